@@ -0,0 +1,183 @@
+use crate::*;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// An error from parsing an ASCII hex map, pointing at a character that doesn't line up with the staggered rows.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct HexAsciiError {
+    /// The 1-based line of the misaligned character.
+    pub line: usize,
+    /// The 1-based column, in characters, of the misaligned character.
+    pub column: usize,
+}
+
+impl fmt::Display for HexAsciiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "hex at line {}, column {} is not aligned with the staggered rows", self.line, self.column)
+    }
+}
+
+impl std::error::Error for HexAsciiError {}
+
+/// Renders the pairs of hexes and values in `data`, such as `HashMapHexData::iter`, as staggered rows of characters, one line per row of hexes.
+///
+/// Hex *(q, r, s)* is drawn on line `r` at column `2q + r`, shifted so the topmost row is the first line and the leftmost hex is in the first column.
+/// Hexes are separated by spaces, trailing spaces are trimmed, and every line ends with a newline.
+/// `symbol` should not return whitespace, which `parse_ascii` reads as an empty cell.
+/// `data` is a sequence of pairs rather than a `&impl HexData<T>` because `HexData` has no way to list its hexes, and adding one would break its existing implementations.
+///
+/// ```
+/// use sturdy_hex::{render_ascii, HashMapHexData, HexCoord, HexData};
+///
+/// let mut data: HashMapHexData<u32> = HashMapHexData::new();
+/// data.insert(HexCoord::new(0, 0), 1);
+/// data.insert(HexCoord::new(1, 0), 2);
+/// data.insert(HexCoord::new(0, 1), 3);
+/// assert_eq!(render_ascii(data.iter(), |value| char::from_digit(*value, 10).unwrap()), "1 2\n 3\n");
+/// ```
+pub fn render_ascii<'a, T: 'a, I: IntoIterator<Item = (HexCoord, &'a T)>, S: Fn(&T) -> char>(data: I, symbol: S) -> String {
+    render_cells(data.into_iter().map(|(hex, value)| (hex, symbol(value))))
+}
+
+/// Renders the hexes of `shape` with `symbol`, laid out as in `render_ascii`.
+pub fn render_ascii_shape(shape: &HexShape, symbol: char) -> String {
+    render_cells(shape.into_iter().map(|hex| (*hex, symbol)))
+}
+
+/// Parses a picture in the format written by `render_ascii`, mapping each non-whitespace character to its hex.
+///
+/// Coordinates are relative to the picture, not to where the hexes were before rendering.
+/// Leading blank lines and indentation common to every line are skipped, and the hex at column `c` of line `r`, counting both from zero after skipping them, has coordinates *(⌊(c - r) / 2⌋, r)*.
+/// Every character must line up with the staggered rows of the first one.
+///
+/// So `parse_ascii(&render_ascii(data.iter(), symbol))` returns the hexes of `data` translated so its topmost row has *r = 0* and its leftmost hex has *2q + r* equal to 0, or -1 if it is on an odd row.
+/// Maps already in that position, like those returned by `parse_ascii`, round-trip exactly.
+pub fn parse_ascii(text: &str) -> Result<HashMapHexData<char>, HexAsciiError> {
+    let mut data = HashMapHexData::new();
+    for (hex, symbol) in parse_cells(text)? {
+        data.insert(hex, symbol);
+    }
+    Ok(data)
+}
+
+/// Parses a picture as in `parse_ascii`, returning a shape with a hex for each non-whitespace character.
+pub fn parse_ascii_shape(text: &str) -> Result<HexShape, HexAsciiError> {
+    Ok(HexShape::new_from_vec(parse_cells(text)?.into_iter().map(|(hex, _)| hex).collect()))
+}
+
+fn render_cells<I: Iterator<Item = (HexCoord, char)>>(cells: I) -> String {
+    // characters by row, then by column
+    let mut rows: BTreeMap<i32, BTreeMap<i32, char>> = BTreeMap::new();
+    for (hex, symbol) in cells {
+        rows.entry(hex.r()).or_default().insert(2 * hex.q() + hex.r(), symbol);
+    }
+
+    let first_row = match rows.keys().next() {
+        Some(row) => *row,
+        None => return String::new(),
+    };
+    let last_row = *rows.keys().next_back().unwrap();
+    let first_column = rows.values().filter_map(|row| row.keys().next()).min().copied().unwrap();
+
+    let mut text = String::new();
+    for r in first_row..=last_row {
+        let mut column = first_column;
+        for (x, symbol) in rows.get(&r).into_iter().flatten() {
+            text.extend(std::iter::repeat(' ').take((x - column) as usize));
+            text.push(*symbol);
+            column = x + 1;
+        }
+        text.push('\n');
+    }
+
+    text
+}
+
+fn parse_cells(text: &str) -> Result<Vec<(HexCoord, char)>, HexAsciiError> {
+    let lines: Vec<&str> = text.lines().collect();
+    let first_line = match lines.iter().position(|line| !line.trim().is_empty()) {
+        Some(line) => line,
+        None => return Ok(Vec::new()),
+    };
+
+    let indentation = lines.iter()
+        .filter_map(|line| line.chars().position(|symbol| !symbol.is_whitespace()))
+        .min()
+        .unwrap();
+
+    let mut parity = None;
+    let mut cells = Vec::new();
+    for (i, line) in lines.iter().enumerate().skip(first_line) {
+        let r = (i - first_line) as i32;
+        for (c, symbol) in line.chars().enumerate() {
+            if symbol.is_whitespace() {
+                continue;
+            }
+
+            let offset = (c - indentation) as i32 - r;
+            if *parity.get_or_insert(offset.rem_euclid(2)) != offset.rem_euclid(2) {
+                return Err(HexAsciiError { line: i + 1, column: c + 1 });
+            }
+            cells.push((HexCoord::new(offset.div_euclid(2), r), symbol));
+        }
+    }
+
+    Ok(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    const BOARD: &str = "# . #\n . @ .\n# . #\n";
+
+    #[test]
+    fn round_trips() {
+        let board = parse_ascii(BOARD).unwrap();
+        assert_eq!(board.get(HexCoord::new(0, 0)), Some(&'#'));
+        assert_eq!(board.get(HexCoord::new(1, 1)), Some(&'@'));
+        assert_eq!(board.get(HexCoord::new(-1, 2)), Some(&'#'));
+        assert_eq!(board.get(HexCoord::new(1, 0)), Some(&'.'));
+        assert_eq!(board.iter().count(), 9);
+        assert_eq!(render_ascii(board.iter(), |symbol| *symbol), BOARD);
+
+        // indentation and leading blank lines don't matter
+        let indented = parse_ascii("\n\n       # . #\n        . @ .\n       # . #").unwrap();
+        assert_eq!(render_ascii(indented.iter(), |symbol| *symbol), BOARD);
+        for (hex, symbol) in board.iter() {
+            assert_eq!(indented.get(hex), Some(symbol));
+        }
+
+        // a map with its topmost row at r = 0 and its leftmost hex at 2q + r = 0 keeps its coordinates
+        let mut data: HashMapHexData<char> = HashMapHexData::new();
+        for (hex, symbol) in [((0, 0), 'a'), ((1, 0), 'b'), ((0, 1), 'c'), ((-1, 2), 'd'), ((2, 3), 'e')] {
+            data.insert(HexCoord::new(hex.0, hex.1), symbol);
+        }
+        let parsed = parse_ascii(&render_ascii(data.iter(), |symbol| *symbol)).unwrap();
+        assert_eq!(parsed.iter().count(), 5);
+        for (hex, symbol) in data.iter() {
+            assert_eq!(parsed.get(hex), Some(symbol));
+        }
+
+        let shape = parse_ascii_shape(BOARD).unwrap();
+        assert_eq!(shape.len(), 9);
+        assert_eq!(render_ascii_shape(&shape, '#'), "# # #\n # # #\n# # #\n");
+    }
+
+    #[test]
+    fn gaps_and_errors() {
+        let shape = HexShape::new_from_vec(vec![HexCoord::new(0, 0), HexCoord::new(-2, 3)]);
+        let rendered = render_ascii_shape(&shape, 'x');
+        assert_eq!(rendered, " x\n\n\nx\n");
+        assert_eq!(parse_ascii_shape(&rendered).unwrap(), shape);
+        let moved = HexShape::new_from_vec(shape.into_iter().map(|hex| *hex + HexCoord::new(5, -7)).collect());
+        assert_eq!(parse_ascii_shape(&render_ascii_shape(&moved, 'x')).unwrap(), shape);
+
+        assert_eq!(render_ascii_shape(&HexShape::new(), 'x'), "");
+        assert!(parse_ascii("  \n\n").unwrap().iter().next().is_none());
+
+        let error = parse_ascii("# #\n# #").err().unwrap();
+        assert_eq!(error, HexAsciiError { line: 2, column: 1 });
+        assert_eq!(error.to_string(), "hex at line 2, column 1 is not aligned with the staggered rows");
+    }
+}
@@ -0,0 +1,491 @@
+use crate::*;
+use std::collections::HashMap;
+use std::fmt;
+
+const MAGIC: &[u8; 4] = b"SHEX";
+const VERSION: u8 = 1;
+
+const HEXAGON: u8 = 0;
+const PARALLELOGRAM: u8 = 1;
+const SPARSE: u8 = 2;
+
+/// An error from encoding or decoding a hex map with `encode_hex_data` or `decode_hex_data`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum HexBinaryError {
+    /// The data doesn't start with the format's magic bytes.
+    InvalidHeader,
+    /// The data was written by an unsupported version of the format.
+    UnsupportedVersion(u8),
+    /// The domain description is unknown or out of range.
+    InvalidDomain,
+    /// The data ended before the map was complete.
+    UnexpectedEnd,
+    /// The data continues after the end of the map.
+    TrailingBytes,
+    /// A hex with a value lies outside the domain it is being encoded with.
+    OutsideDomain(HexCoord),
+    /// The data describes more than one value for the same hex.
+    DuplicateHex(HexCoord),
+    /// A value codec rejected its input.
+    InvalidValue(String),
+}
+
+impl fmt::Display for HexBinaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HexBinaryError::InvalidHeader => write!(f, "not a hex map: invalid header"),
+            HexBinaryError::UnsupportedVersion(version) => write!(f, "unsupported hex map version {}", version),
+            HexBinaryError::InvalidDomain => write!(f, "invalid hex map domain"),
+            HexBinaryError::UnexpectedEnd => write!(f, "unexpected end of hex map data"),
+            HexBinaryError::TrailingBytes => write!(f, "unexpected data after the end of the hex map"),
+            HexBinaryError::OutsideDomain(hex) => write!(f, "hex {} lies outside the encoded domain", hex),
+            HexBinaryError::DuplicateHex(hex) => write!(f, "hex {} appears more than once", hex),
+            HexBinaryError::InvalidValue(message) => write!(f, "invalid value: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for HexBinaryError {}
+
+/// Which hexes a binary hex map describes.
+///
+/// Dense domains store a presence bit for every hex in the domain, so they suit maps that fill most of a region.
+/// Sparse maps store their hexes as runs along rows, with each run's position delta-encoded from the previous one.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum HexDomain {
+    /// The hexes within `radius` of `center`.
+    Hexagon { center: HexCoord, radius: u32 },
+    /// The hexes *(q, r)* with `min.q() <= q < min.q() + width` and `min.r() <= r < min.r() + height`.
+    Parallelogram { min: HexCoord, width: u32, height: u32 },
+    /// Only the hexes that have values.
+    Sparse,
+}
+
+/// Converts values in a hex map to and from bytes for `encode_hex_data` and `decode_hex_data`.
+///
+/// ```
+/// use sturdy_hex::{HexBinaryError, HexValueCodec};
+///
+/// struct ElevationCodec;
+///
+/// impl HexValueCodec<u16> for ElevationCodec {
+///     fn encode(&self, value: &u16, output: &mut Vec<u8>) {
+///         output.extend(value.to_le_bytes());
+///     }
+///
+///     fn decode(&self, input: &mut &[u8]) -> Result<u16, HexBinaryError> {
+///         let (bytes, rest) = input.split_first_chunk::<2>().ok_or(HexBinaryError::UnexpectedEnd)?;
+///         *input = rest;
+///         Ok(u16::from_le_bytes(*bytes))
+///     }
+/// }
+/// ```
+pub trait HexValueCodec<T> {
+    /// Appends the encoding of `value` to `output`.
+    fn encode(&self, value: &T, output: &mut Vec<u8>);
+
+    /// Decodes a value from the start of `input`, advancing `input` past the bytes it used.
+    fn decode(&self, input: &mut &[u8]) -> Result<T, HexBinaryError>;
+}
+
+/// Encodes the pairs of hexes and values in `data`, such as `HashMapHexData::iter`, in a compact, versioned binary format.
+/// The output starts with a header and a description of `domain`, followed by the hexes present in the map and their values encoded with `codec`.
+/// If a hex appears more than once, its last value is used.
+/// Returns an error if a dense `domain` has hexes outside `HexCoord::MAX_COORDINATE`, or if `data` has a value outside it.
+/// `data` is a sequence of pairs rather than a `&impl HexData<T>` because `HexData` has no way to list its hexes, and adding one would break its existing implementations.
+pub fn encode_hex_data<'a, T: 'a, I: IntoIterator<Item = (HexCoord, &'a T)>, C: HexValueCodec<T>>(data: I, domain: HexDomain, codec: &C) -> Result<Vec<u8>, HexBinaryError> {
+    let data: HashMap<HexCoord, &T> = data.into_iter().collect();
+    let mut output = MAGIC.to_vec();
+    output.push(VERSION);
+
+    match domain {
+        HexDomain::Sparse => {
+            let mut hexes: Vec<HexCoord> = data.keys().copied().collect();
+            hexes.sort_by_key(|hex| (hex.r(), hex.q()));
+
+            let runs = row_runs(&hexes);
+            output.push(SPARSE);
+            write_varint(&mut output, runs.len() as u64);
+
+            let (mut previous_r, mut previous_q) = (0, 0);
+            for (start, length) in &runs {
+                write_varint(&mut output, zigzag(start.r() as i64 - previous_r as i64));
+                write_varint(&mut output, zigzag(start.q() as i64 - previous_q as i64));
+                write_varint(&mut output, *length as u64);
+                (previous_r, previous_q) = (start.r(), start.q());
+            }
+
+            for hex in hexes {
+                codec.encode(data[&hex], &mut output);
+            }
+        },
+        _ => {
+            if !domain_in_bounds(domain) {
+                return Err(HexBinaryError::InvalidDomain);
+            }
+            if let Some(hex) = data.keys().find(|hex| !domain_contains(domain, **hex)) {
+                return Err(HexBinaryError::OutsideDomain(*hex));
+            }
+
+            write_domain(&mut output, domain);
+
+            let hexes = domain_hexes(domain);
+            let mut presence = vec![0u8; hexes.len().div_ceil(8)];
+            for (i, hex) in hexes.iter().enumerate() {
+                if data.contains_key(hex) {
+                    presence[i / 8] |= 1 << (i % 8);
+                }
+            }
+            output.extend(presence);
+
+            for hex in hexes {
+                if let Some(value) = data.get(&hex) {
+                    codec.encode(value, &mut output);
+                }
+            }
+        },
+    }
+
+    Ok(output)
+}
+
+/// Decodes a hex map written by `encode_hex_data` into any `HexData` implementation, decoding values with `codec`.
+/// Returns an error if the data is malformed, including if it lists a hex more than once.
+pub fn decode_hex_data<T, D: HexData<T>, C: HexValueCodec<T>>(bytes: &[u8], codec: &C) -> Result<D, HexBinaryError> {
+    let mut input = bytes;
+    if read_bytes(&mut input, MAGIC.len())? != MAGIC {
+        return Err(HexBinaryError::InvalidHeader);
+    }
+    match read_bytes(&mut input, 1)?[0] {
+        VERSION => {},
+        version => return Err(HexBinaryError::UnsupportedVersion(version)),
+    }
+
+    let mut data = D::new();
+    let tag = read_bytes(&mut input, 1)?[0];
+    if tag == SPARSE {
+        let run_count = read_varint(&mut input)?;
+        // each run takes at least three bytes, so a larger count can't be valid
+        if run_count > input.len() as u64 {
+            return Err(HexBinaryError::UnexpectedEnd);
+        }
+
+        let mut runs = Vec::new();
+        let (mut r, mut q) = (0i32, 0i32);
+        for _ in 0..run_count {
+            r = offset(r, unzigzag(read_varint(&mut input)?))?;
+            q = offset(q, unzigzag(read_varint(&mut input)?))?;
+            let length = match i32::try_from(read_varint(&mut input)?) {
+                Ok(length) if length > 0 => length,
+                _ => return Err(HexBinaryError::InvalidDomain),
+            };
+            offset(q, length as i64 - 1)?;
+
+            runs.push((HexCoord::new(q, r), length));
+        }
+
+        for (start, length) in runs {
+            for i in 0..length {
+                let hex = HexCoord::new(start.q() + i, start.r());
+                if data.insert(hex, codec.decode(&mut input)?).is_some() {
+                    return Err(HexBinaryError::DuplicateHex(hex));
+                }
+            }
+        }
+    } else {
+        let domain = read_domain(&mut input, tag)?;
+        let hexes = domain_hexes(domain);
+        let presence = read_bytes(&mut input, hexes.len().div_ceil(8))?;
+
+        for (i, hex) in hexes.into_iter().enumerate() {
+            if presence[i / 8] & (1 << (i % 8)) != 0 {
+                data.insert(hex, codec.decode(&mut input)?);
+            }
+        }
+    }
+
+    if input.is_empty() {
+        Ok(data)
+    } else {
+        Err(HexBinaryError::TrailingBytes)
+    }
+}
+
+// runs of hexes with consecutive q in the same row, as (first hex, length); `hexes` must be sorted by row, then q
+fn row_runs(hexes: &[HexCoord]) -> Vec<(HexCoord, u32)> {
+    let mut runs: Vec<(HexCoord, u32)> = Vec::new();
+    for hex in hexes {
+        match runs.last_mut() {
+            Some((start, length)) if start.r() == hex.r() && start.q() as i64 + *length as i64 == hex.q() as i64 => *length += 1,
+            _ => runs.push((*hex, 1)),
+        }
+    }
+    runs
+}
+
+// whether every hex of a dense domain is within `HexCoord::MAX_COORDINATE`, checked at its extreme corners
+fn domain_in_bounds(domain: HexDomain) -> bool {
+    let corners: Vec<Option<HexCoord>> = match domain {
+        HexDomain::Hexagon { center, radius } => match i32::try_from(radius) {
+            Ok(radius) => HexDirection::iter()
+                .map(|direction| HexCoord::get_unit_coord(direction).checked_mul(radius).and_then(|offset| center.checked_add(offset)))
+                .collect(),
+            Err(_) => return false,
+        },
+        HexDomain::Parallelogram { width: 0, .. } | HexDomain::Parallelogram { height: 0, .. } => return true,
+        HexDomain::Parallelogram { min, width, height } => match (i32::try_from(width - 1), i32::try_from(height - 1)) {
+            (Ok(q), Ok(r)) => [HexCoord::ZERO, HexCoord::new(q, 0), HexCoord::new(0, r), HexCoord::new(q, r)]
+                .into_iter()
+                .map(|offset| min.checked_add(offset))
+                .collect(),
+            _ => return false,
+        },
+        HexDomain::Sparse => return true,
+    };
+
+    corners.iter().all(Option::is_some)
+}
+
+fn domain_contains(domain: HexDomain, hex: HexCoord) -> bool {
+    match domain {
+        // in a domain within bounds, the offset from the center to any of its hexes is also within bounds
+        HexDomain::Hexagon { center, radius } => match hex.checked_sub(center) {
+            Some(offset) => HexCoord::hex_distance(offset, HexCoord::ZERO) as i64 <= radius as i64,
+            None => false,
+        },
+        HexDomain::Parallelogram { min, width, height } => {
+            let (q, r) = (hex.q() as i64 - min.q() as i64, hex.r() as i64 - min.r() as i64);
+            (0..width as i64).contains(&q) && (0..height as i64).contains(&r)
+        },
+        HexDomain::Sparse => true,
+    }
+}
+
+fn domain_hexes(domain: HexDomain) -> Vec<HexCoord> {
+    match domain {
+        HexDomain::Hexagon { center, radius } => HexRangeIterator::new(radius, HexTransform::new(center, 0)).collect(),
+        // an empty parallelogram has no hexes however tall it is, so its rows aren't visited
+        HexDomain::Parallelogram { width: 0, .. } | HexDomain::Parallelogram { height: 0, .. } => Vec::new(),
+        HexDomain::Parallelogram { min, width, height } => (0..height as i32)
+            .flat_map(|r| (0..width as i32).map(move |q| HexCoord::new(min.q() + q, min.r() + r)))
+            .collect(),
+        HexDomain::Sparse => Vec::new(),
+    }
+}
+
+fn write_domain(output: &mut Vec<u8>, domain: HexDomain) {
+    match domain {
+        HexDomain::Hexagon { center, radius } => {
+            output.push(HEXAGON);
+            write_varint(output, zigzag(center.q() as i64));
+            write_varint(output, zigzag(center.r() as i64));
+            write_varint(output, radius as u64);
+        },
+        HexDomain::Parallelogram { min, width, height } => {
+            output.push(PARALLELOGRAM);
+            write_varint(output, zigzag(min.q() as i64));
+            write_varint(output, zigzag(min.r() as i64));
+            write_varint(output, width as u64);
+            write_varint(output, height as u64);
+        },
+        HexDomain::Sparse => output.push(SPARSE),
+    }
+}
+
+fn read_domain(input: &mut &[u8], tag: u8) -> Result<HexDomain, HexBinaryError> {
+    let domain = match tag {
+        HEXAGON => HexDomain::Hexagon {
+            center: HexCoord::new(read_i32(input)?, read_i32(input)?),
+            radius: read_u32(input)?,
+        },
+        PARALLELOGRAM => HexDomain::Parallelogram {
+            min: HexCoord::new(read_i32(input)?, read_i32(input)?),
+            width: read_u32(input)?,
+            height: read_u32(input)?,
+        },
+        _ => return Err(HexBinaryError::InvalidDomain),
+    };
+
+    // the presence bits must fit in the remaining input, which bounds the domain before allocating it
+    let count = match domain {
+        HexDomain::Hexagon { radius, .. } => 3 * radius as u64 * (radius as u64 + 1) + 1,
+        HexDomain::Parallelogram { width, height, .. } => width as u64 * height as u64,
+        HexDomain::Sparse => 0,
+    };
+    if count.div_ceil(8) > input.len() as u64 {
+        return Err(HexBinaryError::UnexpectedEnd);
+    }
+
+    // every hex in the domain must have coordinates that fit in an i32
+    let in_range = |x: i64| (i32::MIN as i64..=i32::MAX as i64).contains(&x);
+    let fits = match domain {
+        HexDomain::Hexagon { center, radius } => [center.q(), center.r(), center.s()]
+            .iter()
+            .all(|x| in_range(*x as i64 - radius as i64) && in_range(*x as i64 + radius as i64)),
+        HexDomain::Parallelogram { min, width, height } => in_range(min.q() as i64 + width as i64) && in_range(min.r() as i64 + height as i64),
+        HexDomain::Sparse => true,
+    };
+    if fits { Ok(domain) } else { Err(HexBinaryError::InvalidDomain) }
+}
+
+fn read_bytes<'a>(input: &mut &'a [u8], count: usize) -> Result<&'a [u8], HexBinaryError> {
+    if input.len() < count {
+        return Err(HexBinaryError::UnexpectedEnd);
+    }
+    let (bytes, rest) = input.split_at(count);
+    *input = rest;
+    Ok(bytes)
+}
+
+fn write_varint(output: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        output.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    output.push(value as u8);
+}
+
+fn read_varint(input: &mut &[u8]) -> Result<u64, HexBinaryError> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = read_bytes(input, 1)?[0];
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(HexBinaryError::InvalidDomain)
+}
+
+fn read_i32(input: &mut &[u8]) -> Result<i32, HexBinaryError> {
+    i32::try_from(unzigzag(read_varint(input)?)).map_err(|_| HexBinaryError::InvalidDomain)
+}
+
+fn read_u32(input: &mut &[u8]) -> Result<u32, HexBinaryError> {
+    u32::try_from(read_varint(input)?).map_err(|_| HexBinaryError::InvalidDomain)
+}
+
+// adds a decoded delta to a coordinate, rejecting results that don't fit in an i32
+fn offset(coordinate: i32, delta: i64) -> Result<i32, HexBinaryError> {
+    (coordinate as i64).checked_add(delta)
+        .and_then(|sum| i32::try_from(sum).ok())
+        .ok_or(HexBinaryError::InvalidDomain)
+}
+
+// maps signed integers to unsigned so small magnitudes encode in few bytes
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    struct U16Codec;
+
+    impl HexValueCodec<u16> for U16Codec {
+        fn encode(&self, value: &u16, output: &mut Vec<u8>) {
+            output.extend(value.to_le_bytes());
+        }
+
+        fn decode(&self, input: &mut &[u8]) -> Result<u16, HexBinaryError> {
+            let (bytes, rest) = input.split_first_chunk::<2>().ok_or(HexBinaryError::UnexpectedEnd)?;
+            *input = rest;
+            Ok(u16::from_le_bytes(*bytes))
+        }
+    }
+
+    fn map(hexes: impl Iterator<Item = HexCoord>) -> HashMapHexData<u16> {
+        let mut data = HashMapHexData::new();
+        for (i, hex) in hexes.enumerate() {
+            data.insert(hex, i as u16 * 7);
+        }
+        data
+    }
+
+    fn assert_same(a: &HashMapHexData<u16>, b: &HashMapHexData<u16>) {
+        assert_eq!(a.iter().count(), b.iter().count());
+        for (hex, value) in a.iter() {
+            assert_eq!(b.get(hex), Some(value));
+        }
+    }
+
+    #[test]
+    fn domains_round_trip() {
+        let center = HexCoord::new(-40, 25);
+        let mut data = map(HexRangeIterator::new(3, HexTransform::new(center, 0)));
+        data.remove(center);
+
+        let domains = [
+            HexDomain::Hexagon { center, radius: 3 },
+            HexDomain::Parallelogram { min: HexCoord::new(-43, 22), width: 7, height: 7 },
+            HexDomain::Sparse,
+        ];
+        for domain in domains {
+            let bytes = encode_hex_data(data.iter(), domain, &U16Codec).unwrap();
+            assert_eq!(&bytes[..5], b"SHEX\x01");
+
+            let decoded: HashMapHexData<u16> = decode_hex_data(&bytes, &U16Codec).unwrap();
+            assert_same(&data, &decoded);
+        }
+
+        let empty: HashMapHexData<u16> = HashMapHexData::new();
+        let bytes = encode_hex_data(empty.iter(), HexDomain::Sparse, &U16Codec).unwrap();
+        assert_eq!(bytes, b"SHEX\x01\x02\x00");
+        assert_eq!(decode_hex_data::<u16, HashMapHexData<u16>, _>(&bytes, &U16Codec).unwrap().iter().count(), 0);
+    }
+
+    #[test]
+    fn sparse_rows_are_run_length_encoded() {
+        let row = map((-50..50).map(|q| HexCoord::new(q, 1000)));
+        let bytes = encode_hex_data(row.iter(), HexDomain::Sparse, &U16Codec).unwrap();
+
+        // header, domain tag, one run, and the values
+        assert!(bytes.len() <= 6 + 1 + 6 + 2 * 100);
+        let decoded: HashMapHexData<u16> = decode_hex_data(&bytes, &U16Codec).unwrap();
+        assert_same(&row, &decoded);
+    }
+
+    #[test]
+    fn invalid_input() {
+        let data = map([HexCoord::new(5, 0)].into_iter());
+        let hexagon = HexDomain::Hexagon { center: HexCoord::ZERO, radius: 2 };
+        assert_eq!(encode_hex_data(data.iter(), hexagon, &U16Codec), Err(HexBinaryError::OutsideDomain(HexCoord::new(5, 0))));
+
+        let bytes = encode_hex_data(data.iter(), HexDomain::Sparse, &U16Codec).unwrap();
+        let decode = |bytes: &[u8]| decode_hex_data::<u16, HashMapHexData<u16>, _>(bytes, &U16Codec).err();
+        assert_eq!(decode(&bytes), None);
+        assert_eq!(decode(&bytes[..bytes.len() - 1]), Some(HexBinaryError::UnexpectedEnd));
+        assert_eq!(decode(&[bytes.as_slice(), &[0]].concat()), Some(HexBinaryError::TrailingBytes));
+        assert_eq!(decode(b"NOPE\x01\x02\x00"), Some(HexBinaryError::InvalidHeader));
+        assert_eq!(decode(b"SHEX\x02\x02\x00"), Some(HexBinaryError::UnsupportedVersion(2)));
+        assert_eq!(decode(b"SHEX\x01\x07"), Some(HexBinaryError::InvalidDomain));
+
+        // two runs that overlap at (1, 0, -1)
+        assert_eq!(decode(b"SHEX\x01\x02\x02\x00\x00\x02\x00\x02\x01\x00\x00\x00\x00\x00\x00"), Some(HexBinaryError::DuplicateHex(HexCoord::new(1, 0))));
+
+        // hexes far outside a domain are rejected without overflowing, and so are domains beyond `MAX_COORDINATE`
+        let far = map([HexCoord::new(i32::MAX, 0)].into_iter());
+        let max = HexCoord::MAX_COORDINATE;
+        let edge = HexDomain::Hexagon { center: HexCoord::new(-max + 2, 0), radius: 2 };
+        assert_eq!(encode_hex_data(far.iter(), edge, &U16Codec), Err(HexBinaryError::OutsideDomain(HexCoord::new(i32::MAX, 0))));
+        let beyond = HexDomain::Hexagon { center: HexCoord::new(-max + 2, 0), radius: 3 };
+        assert_eq!(encode_hex_data(data.iter(), beyond, &U16Codec), Err(HexBinaryError::InvalidDomain));
+        let wide = HexDomain::Parallelogram { min: HexCoord::ZERO, width: max as u32 + 2, height: 1 };
+        assert_eq!(encode_hex_data(data.iter(), wide, &U16Codec), Err(HexBinaryError::InvalidDomain));
+
+        // a huge domain is rejected before allocating it
+        assert_eq!(decode(b"SHEX\x01\x01\x00\x00\xff\xff\xff\x0f\xff\xff\xff\x0f"), Some(HexBinaryError::UnexpectedEnd));
+
+        // an empty but very tall parallelogram decodes without visiting its rows
+        let tall = HexDomain::Parallelogram { min: HexCoord::ZERO, width: 0, height: i32::MAX as u32 };
+        let empty: HashMapHexData<u16> = HashMapHexData::new();
+        let bytes = encode_hex_data(empty.iter(), tall, &U16Codec).unwrap();
+        assert_eq!(bytes, b"SHEX\x01\x01\x00\x00\x00\xff\xff\xff\xff\x07");
+        assert_eq!(decode(&bytes), None);
+    }
+}
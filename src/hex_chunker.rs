@@ -1,36 +1,44 @@
 use crate::*;
 
+/// Converts between hexes and the hex-shaped chunks of a given radius that tile the grid.
+/// Conversions are exact for hexes within `HexCoord::MAX_COORDINATE` and chunk radii up to `HexCoord::MAX_COORDINATE`.
 pub struct HexChunker {
     chunk_radius: u32,
-    area: u32,
-    shift: u32,
+    area: i64,
+    shift: i64,
 }
 
 impl HexChunker {
     pub fn new(chunk_radius: u32) -> HexChunker {
+        assert!(chunk_radius <= HexCoord::MAX_COORDINATE as u32, "chunk radius is larger than HexCoord::MAX_COORDINATE");
+        let radius = chunk_radius as i64;
         HexChunker {
             chunk_radius,
-            area: 3 * chunk_radius * chunk_radius + 3 * chunk_radius + 1,
-            shift: 3 * chunk_radius + 2,
+            area: 3 * radius * radius + 3 * radius + 1,
+            shift: 3 * radius + 2,
         }
     }
 
     pub fn get_chunk_center(&self, chunk_coord: HexCoord) -> HexCoord {
+        let radius = self.chunk_radius as i64;
+        let (q, r) = (chunk_coord.q() as i64, chunk_coord.r() as i64);
         HexCoord::new(
-            (2 * self.chunk_radius + 1) as i32 * chunk_coord.q() + self.chunk_radius as i32 * chunk_coord.r(),
-            self.chunk_radius as i32 * -chunk_coord.q() + (self.chunk_radius + 1) as i32 * chunk_coord.r(),
+            ((2 * radius + 1) * q + radius * r) as i32,
+            (radius * -q + (radius + 1) * r) as i32,
         )
     }
 
     // algo from https://observablehq.com/@sanderevers/hexagon-tiling-of-an-hexagonal-grid
+    // computed in i64, since `shift` times a coordinate can overflow an i32
     pub fn get_containing_chunk(&self, coord: HexCoord) -> HexCoord {
-        let xh = (coord.r() + self.shift as i32 * coord.q()).div_euclid(self.area as i32);
-        let yh = (coord.s() + self.shift as i32 * coord.r()).div_euclid(self.area as i32);
-        let zh = (coord.q() + self.shift as i32 * coord.s()).div_euclid(self.area as i32);
+        let (q, r, s) = (coord.q() as i64, coord.r() as i64, coord.s() as i64);
+        let xh = (r + self.shift * q).div_euclid(self.area);
+        let yh = (s + self.shift * r).div_euclid(self.area);
+        let zh = (q + self.shift * s).div_euclid(self.area);
 
         HexCoord::new (
-            (1 + xh - yh).div_euclid(3),
-            (1 + yh - zh).div_euclid(3),
+            (1 + xh - yh).div_euclid(3) as i32,
+            (1 + yh - zh).div_euclid(3) as i32,
         )
     }
-}
\ No newline at end of file
+}
@@ -1,5 +1,5 @@
-use std::ops;
-use crate::{*, hex_range_iterator::{HexVertexIterator, HexHalfEdgeIterator, HexNeighborIterator}};
+use std::{fmt, ops, str::FromStr};
+use crate::{*, hex_parse::parse_components, hex_range_iterator::{HexVertexIterator, HexHalfEdgeIterator, HexNeighborIterator, HexDiagonalIterator}};
 
 /// A coordinate specifying a hex on a hex grid.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
@@ -39,33 +39,69 @@ impl HexCoord {
     /// A hex coordinate representing the origin of the hex coordinate system.
     pub const ZERO: HexCoord = HexCoord{q: 0, r: 0};
 
+    /// The largest magnitude of *q*, *r*, or *s* for which SturdyHex guarantees exact results.
+    /// For hexes within these bounds, `hex_distance`, `diagonal_distance`, rotation, reflection, transforms, conversion to vertices and half-edges, and `HexChunker` conversions never overflow, and neither does adding or subtracting two such hexes.
+    /// Use the `checked_` methods to detect results that leave the bounds.
+    /// Conversion to `HexCoordFraction<f32>` is only exact for coordinates up to 2^24; use `f64` beyond that.
+    pub const MAX_COORDINATE: i32 = (1 << 28) - 1;
+
     // methods
     /// Returns a hex coordinate representing one of the six unit directions from a hex to its neighbors.
-    /// Parameter `i` specifies which neighbor.
-    /// For convenience, `i` is wrapped (not clamped) to the range [0, 5].
-    pub fn get_unit_coord(i: i32) -> HexCoord {
-        match i.rem_euclid(6) {
-            0 => HexCoord::new(1, -1),
-            1 => HexCoord::new(1, 0),
-            2 => HexCoord::new(0, 1),
-            3 => HexCoord::new(-1, 1),
-            4 => HexCoord::new(-1, 0),
-            5 => HexCoord::new(0, -1),
-            _ => panic!("logic error; rem_euclid(6) should return one of the previous cases"),
+    /// Parameter `i` specifies which neighbor, as a `HexDirection` or an `i32`.
+    /// For convenience, an `i32` is wrapped (not clamped) to the range [0, 5].
+    pub fn get_unit_coord<D: Into<HexDirection>>(i: D) -> HexCoord {
+        match i.into() {
+            HexDirection::Zero => HexCoord::new(1, -1),
+            HexDirection::One => HexCoord::new(1, 0),
+            HexDirection::Two => HexCoord::new(0, 1),
+            HexDirection::Three => HexCoord::new(-1, 1),
+            HexDirection::Four => HexCoord::new(-1, 0),
+            HexDirection::Five => HexCoord::new(0, -1),
         }
     }
 
-    pub fn get_neighbor(&self, i: i32) -> HexCoord {
+    pub fn get_neighbor<D: Into<HexDirection>>(&self, i: D) -> HexCoord {
         *self + Self::get_unit_coord(i)
     }
 
+    /// Returns a hex coordinate representing one of the six diagonal directions, to the hexes at distance 2 that lie between two neighbors.
+    /// Diagonal `i` is the sum of unit directions `i` and `i + 1`, and points through vertex `i`, so `i` is a `HexCorner` or an `i32`.
+    /// For convenience, an `i32` is wrapped (not clamped) to the range [0, 5].
+    pub fn get_diagonal_coord<C: Into<HexCorner>>(i: C) -> HexCoord {
+        let i = i.into().index();
+        HexCoord::get_unit_coord(i) + HexCoord::get_unit_coord(i + 1)
+    }
+
+    /// Returns the `i`th diagonal hex of `self`, which shares an edge with neighbors `i` and `i + 1` but shares no vertex with `self`.
+    pub fn get_diagonal<C: Into<HexCorner>>(&self, i: C) -> HexCoord {
+        *self + Self::get_diagonal_coord(i)
+    }
+
+    /// Returns the half-edge between neighbors `i` and `i + 1` of `self`, leading from vertex `i` of `self` to a vertex of diagonal `i`.
+    /// Its `diagonal_hexes` are `self` and `get_diagonal(i)`.
+    pub fn get_diagonal_edge<C: Into<HexCorner>>(&self, i: C) -> HexHalfEdge {
+        let i = i.into();
+        self.get_neighbor(i.index() + 1).get_half_edge(i + 4)
+    }
+
+    /// Returns an iterator over the diagonal hexes of `self`.
+    pub fn diagonals(&self) -> HexDiagonalIterator {
+        HexDiagonalIterator::new(*self)
+    }
+
+    /// Returns the direction from `self` to `other` if they are neighbors, or `None` otherwise.
+    pub fn direction_to(&self, other: HexCoord) -> Option<HexDirection> {
+        let offset = other.checked_sub(*self)?;
+        HexDirection::iter().find(|direction| direction.unit_coord() == offset)
+    }
+
     pub fn neighbors(&self) -> HexNeighborIterator {
         HexNeighborIterator::new(*self)
     }
 
-    /// Returns the `i`th vertex of `self`.
-    /// For convenience, `i` is wrapped (not clamped) to the range [0, 5].
-    pub fn get_vertex(&self, i: i32) -> HexVertex {
+    /// Returns the `i`th vertex of `self`, where `i` is a `HexCorner` or an `i32`.
+    /// For convenience, an `i32` is wrapped (not clamped) to the range [0, 5].
+    pub fn get_vertex<C: Into<HexCorner>>(&self, i: C) -> HexVertex {
         HexVertex::get_unit_coord(i).translate(self)
     }
 
@@ -74,9 +110,10 @@ impl HexCoord {
         HexVertexIterator::new(*self)
     }
 
-    /// Returns the `i`th half-edge of `self`.
-    /// For convenience, `i` is wrapped (not clamped) to the range [0, 5].
-    pub fn get_half_edge(&self, i: i32) -> HexHalfEdge {
+    /// Returns the `i`th half-edge of `self`, which runs from corner `i` to corner `i + 1`, where `i` is a `HexCorner` or an `i32`.
+    /// For convenience, an `i32` is wrapped (not clamped) to the range [0, 5].
+    pub fn get_half_edge<C: Into<HexCorner>>(&self, i: C) -> HexHalfEdge {
+        let i = i.into();
         HexHalfEdge::new(
             self.get_vertex(i),
             self.get_vertex(i+1),
@@ -104,12 +141,13 @@ impl HexCoord {
         }
     }
 
-    /// Returns a hex coordinate representing `self` rotated `i` sixth-turns CCW around `pivot`.
-    pub fn rotate_around(&self, pivot: HexCoord, rotation: i32) -> HexCoord {
+    /// Returns a hex coordinate representing `self` rotated around `pivot` by `rotation`, which turns `HexDirection::Zero` to the given direction.
+    /// An `i32` rotation counts sixth-turns CCW.
+    pub fn rotate_around<R: Into<HexDirection>>(&self, pivot: HexCoord, rotation: R) -> HexCoord {
         let relative = *self - pivot;
         
         // mathematica: Table[{{0,-1},{1,1}}^n.{q,r},{n,0,5}]
-        let relative = match rotation.rem_euclid(6) {
+        let relative = match rotation.into().index() {
             0 => HexCoord::new(relative.q(), relative.r()),
             1 => HexCoord::new(-relative.r(), relative.q() + relative.r()),
             2 => HexCoord::new(-relative.q() - relative.r(), relative.q()),
@@ -122,19 +160,65 @@ impl HexCoord {
         pivot + relative
     }
 
+    /// Returns a hex coordinate representing `self` reflected across the line through the origin and hex *(2, -1, -1)*.
+    /// The reflection keeps the *q* coordinate and swaps the *r* and *s* coordinates.
+    pub fn reflect(&self) -> HexCoord {
+        HexCoord {
+            q: self.q(),
+            r: self.s(),
+        }
+    }
+
     /// Returns the number of hexes in the shortest path along the hex grid from `a` to `b`.
     /// Includes `b` in the count but not `a`; if `a` and `b` are neighbors, `hex_distance(a, b)` returns 1.
     pub fn hex_distance(a: HexCoord, b: HexCoord) -> i32 {
         ((a.q() - b.q()).abs() + (a.r() - b.r()).abs() + (a.s() - b.s()).abs()) / 2
     }
 
+    /// Returns the number of moves in the shortest path from `a` to `b` when each move may go to a neighbor or to a diagonal.
+    /// This is about half of `hex_distance` in the directions of the diagonals, and two thirds of it in the directions of the neighbors.
+    pub fn diagonal_distance(a: HexCoord, b: HexCoord) -> i32 {
+        let offset = a - b;
+        let extent = (offset.q() - offset.r()).abs()
+            .max((offset.r() - offset.s()).abs())
+            .max((offset.s() - offset.q()).abs());
+        (extent + 2) / 3
+    }
+
+    /// Returns whether the *q*, *r*, and *s* coordinates of `self` are all within `MAX_COORDINATE` of zero.
+    pub fn is_in_bounds(&self) -> bool {
+        HexCoord::checked_from_i64(self.q() as i64, self.r() as i64).is_some()
+    }
+
+    /// Returns `self + other`, or `None` if the result is outside the bounds given by `MAX_COORDINATE`.
+    pub fn checked_add(&self, other: HexCoord) -> Option<HexCoord> {
+        HexCoord::checked_from_i64(self.q() as i64 + other.q() as i64, self.r() as i64 + other.r() as i64)
+    }
+
+    /// Returns `self - other`, or `None` if the result is outside the bounds given by `MAX_COORDINATE`.
+    pub fn checked_sub(&self, other: HexCoord) -> Option<HexCoord> {
+        HexCoord::checked_from_i64(self.q() as i64 - other.q() as i64, self.r() as i64 - other.r() as i64)
+    }
 
+    /// Returns `self * scale`, or `None` if the result is outside the bounds given by `MAX_COORDINATE`.
+    pub fn checked_mul(&self, scale: i32) -> Option<HexCoord> {
+        HexCoord::checked_from_i64(self.q() as i64 * scale as i64, self.r() as i64 * scale as i64)
+    }
 
+    // i64 can't overflow for sums or products of two i32 values, so the bounds check sees the exact result
+    fn checked_from_i64(q: i64, r: i64) -> Option<HexCoord> {
+        let max = HexCoord::MAX_COORDINATE as i64;
+        if q.abs() > max || r.abs() > max || (q + r).abs() > max {
+            return None;
+        }
 
+        Some(HexCoord::new(q as i32, r as i32))
+    }
 }
 
 
 // operator overloads
+/// Arithmetic on `HexCoord` wraps or panics like `i32` arithmetic when results overflow; see `HexCoord::MAX_COORDINATE` and the `checked_` methods.
 impl ops::Add for HexCoord {
     type Output = HexCoord;
 
@@ -208,6 +292,22 @@ impl ops::MulAssign::<i32> for HexCoord {
     }
 }
 
+impl fmt::Display for HexCoord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.q(), self.r(), self.s())
+    }
+}
+
+/// Parses coordinates written as `(q, r, s)` or `(q, r)`, with or without the parentheses.
+impl FromStr for HexCoord {
+    type Err = HexParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (q, r) = parse_components(s, |component| component.parse().ok())?;
+        Ok(HexCoord::new(q, r))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::*;
@@ -220,4 +320,141 @@ mod tests {
         }
         assert_eq!(sum, HexCoord::ZERO);
     }
+
+    #[test]
+    fn vertex_edges_keep_their_indices() {
+        let vertex = HexCoord::ZERO.get_vertex(0);
+        assert_eq!(vertex.get_outgoing_edge(0).destination(), HexVertex::new(4, -2));
+        assert_eq!(vertex.get_outgoing_edge(1).destination(), HexVertex::new(1, 1));
+        assert_eq!(vertex.get_outgoing_edge(2).destination(), HexVertex::new(1, -2));
+        assert_eq!(vertex.get_outgoing_edge(1), vertex.get_outgoing_edge_toward(HexCorner::Two));
+        assert_eq!(vertex.get_incoming_edge(2), vertex.get_incoming_edge_from(HexCorner::Four));
+
+        let vertex = HexCoord::ZERO.get_vertex(1);
+        assert_eq!(vertex.get_outgoing_edge(0).destination(), HexVertex::new(2, 2));
+        assert_eq!(vertex.get_outgoing_edge(1), vertex.get_outgoing_edge_toward(HexCorner::Three));
+    }
+
+    #[test]
+    fn diagonals_lie_between_neighbors() {
+        let hex = HexCoord::new(-2, 3);
+        assert_eq!(hex.diagonals().count(), 6);
+        for (i, diagonal) in hex.diagonals().enumerate() {
+            let i = i as i32;
+            assert_eq!(diagonal, hex.get_diagonal(HexCorner::new(i)));
+            assert_eq!(HexCoord::hex_distance(hex, diagonal), 2);
+            assert_eq!(HexCoord::hex_distance(hex.get_neighbor(i), diagonal), 1);
+            assert_eq!(HexCoord::hex_distance(hex.get_neighbor(i + 1), diagonal), 1);
+
+            let edge = hex.get_diagonal_edge(i);
+            assert_eq!(edge.source(), hex.get_vertex(i));
+            assert_eq!(edge.diagonal_hexes(), (hex, diagonal));
+            assert_eq!(edge.destination(), diagonal.get_vertex(i + 3));
+            assert_eq!((edge.hex(), edge.twin().hex()), (hex.get_neighbor(i + 1), hex.get_neighbor(i)));
+
+            let vertex = hex.get_vertex(i);
+            assert!(vertex.hexes().contains(&hex.get_neighbor(i)));
+            assert_eq!(vertex.diagonal_beyond(hex), Some(diagonal));
+            assert_eq!(vertex.diagonal_beyond(hex.get_diagonal(i + 3)), None);
+
+            let (source_side, destination_side) = hex.get_half_edge(i).diagonal_hexes();
+            assert_eq!(source_side, hex.get_neighbor(i));
+            assert_eq!(source_side.get_diagonal(i + 2), destination_side);
+        }
+
+        for vertex in hex.vertices() {
+            let mut hexes = vertex.hexes();
+            hexes.sort();
+            let mut expected: Vec<HexCoord> = HexShape::new_hexagon(1).into_iter()
+                .map(|offset| hex + *offset)
+                .filter(|candidate| candidate.vertices().any(|other| other == vertex))
+                .collect();
+            expected.sort();
+            assert_eq!(hexes.to_vec(), expected);
+        }
+    }
+
+    #[test]
+    fn diagonal_distance_matches_search() {
+        // breadth-first search over moves to neighbors and diagonals
+        let origin = HexCoord::new(3, -1);
+        let mut distances = std::collections::HashMap::from([(origin, 0)]);
+        let mut frontier = vec![origin];
+        for distance in 1..=4 {
+            frontier = frontier.iter()
+                .flat_map(|hex| hex.neighbors().chain(hex.diagonals()))
+                .filter(|hex| !distances.contains_key(hex))
+                .collect();
+            for hex in &frontier {
+                distances.insert(*hex, distance);
+            }
+        }
+
+        for hex in HexRangeIterator::new(6, HexTransform::from_translation(origin)) {
+            assert_eq!(distances.get(&hex).copied(), Some(HexCoord::diagonal_distance(origin, hex)).filter(|distance| *distance <= 4));
+        }
+        assert_eq!(HexCoord::diagonal_distance(origin, origin.get_diagonal(1)), 1);
+        assert_eq!(HexCoord::diagonal_distance(origin, origin + HexCoord::new(4, -2)), 2);
+        assert_eq!(HexCoord::diagonal_distance(origin, origin + HexCoord::new(4, 0)), 3);
+
+        // a straight run toward a neighbor takes two thirds as many moves as hexes
+        assert_eq!(HexCoord::diagonal_distance(origin, origin + HexCoord::new(6, 0)), 4);
+        assert_eq!(HexCoord::diagonal_distance(origin, origin + HexCoord::new(0, -9)), 6);
+    }
+
+    #[test]
+    fn bounded_operations_are_exact() {
+        let max = HexCoord::MAX_COORDINATE;
+        let extremes = [(max, 0), (-max, 0), (0, max), (0, -max), (max, -max), (-max, max)].map(|(q, r)| HexCoord::new(q, r));
+        for a in extremes {
+            for b in extremes {
+                let (dq, dr) = (a.q() as i64 - b.q() as i64, a.r() as i64 - b.r() as i64);
+                let distance = (dq.abs() + dr.abs() + (dq + dr).abs()) / 2;
+                assert_eq!(HexCoord::hex_distance(a, b) as i64, distance);
+                assert!(HexCoord::diagonal_distance(a, b) as i64 * 2 >= distance);
+                assert_eq!(a + b - b, a);
+                for rotation in 0..6 {
+                    assert_eq!(a.rotate_around(b, rotation).rotate_around(b, -rotation), a);
+                    let transform = HexTransform::new(b, rotation);
+                    assert_eq!(transform.inverse() * (transform * a), a);
+                }
+            }
+
+            assert_eq!(a.reflect().reflect(), a);
+            assert_eq!(a.rotate_back().rotate_forward(), a);
+            for corner in HexCorner::iter() {
+                assert!(a.get_vertex(corner).hexes().contains(&a));
+                assert_eq!(a.get_half_edge(corner).hex(), a);
+                assert_eq!(a.get_half_edge(corner).next().prev(), a.get_half_edge(corner));
+            }
+            for radius in [1, 5, 1000, max] {
+                let chunker = HexChunker::new(radius as u32);
+                let center = chunker.get_chunk_center(chunker.get_containing_chunk(a));
+                assert!(HexCoord::hex_distance(a, center) <= radius);
+            }
+        }
+    }
+
+    #[test]
+    fn checked_arithmetic_respects_bounds() {
+        let max = HexCoord::MAX_COORDINATE;
+        let edge = HexCoord::new(max, 0);
+        assert!(edge.is_in_bounds());
+        assert!(!HexCoord::new(max, 1).is_in_bounds());
+
+        assert_eq!(edge.checked_add(HexCoord::new(-1, 1)), Some(HexCoord::new(max - 1, 1)));
+        assert_eq!(edge.checked_add(HexCoord::new(1, -1)), None);
+        assert_eq!(edge.checked_sub(HexCoord::new(-1, 0)), None);
+        assert_eq!(HexCoord::new(i32::MIN, 0).checked_sub(HexCoord::new(1, 0)), None);
+        assert_eq!(HexCoord::new(3, -1).checked_mul(-2), Some(HexCoord::new(-6, 2)));
+        assert_eq!(edge.checked_mul(2), None);
+        assert_eq!(HexCoord::new(i32::MAX, 0).checked_mul(i32::MAX), None);
+
+        let far = HexCoord::new(-max, max);
+        assert_eq!(HexCoord::hex_distance(edge, far), 2 * max);
+        let corner = far.get_vertex(2);
+        assert_eq!((corner.three_q(), corner.three_r()), (-3 * max - 1, 3 * max + 2));
+        assert_eq!(far.get_vertex(2).checked_translate(&HexCoord::new(1, -1)), Some(far.get_vertex(2).translate(&HexCoord::new(1, -1))));
+        assert_eq!(far.get_vertex(3).checked_translate(&HexCoord::new(-1, 0)), None);
+    }
 }
\ No newline at end of file
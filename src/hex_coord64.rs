@@ -0,0 +1,660 @@
+use std::{fmt, ops};
+use crate::*;
+
+/// A coordinate specifying a hex on a hex grid, like `HexCoord` but with 64-bit coordinates for grids too large for `i32`.
+/// Converts losslessly from `HexCoord`, and back with `TryFrom` when the coordinates are within `HexCoord::MAX_COORDINATE`.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct HexCoord64 {
+    q: i64,
+    r: i64,
+}
+
+impl HexCoord64 {
+    // accessors
+    /// Returns the *q* coordinate of the hex in the cubical coordinate system described [here](https://www.redblobgames.com/grids/hexagons/).
+    pub fn q(&self) -> i64 {
+        self.q
+    }
+
+    /// Returns the *r* coordinate of the hex in the cubical coordinate system described [here](https://www.redblobgames.com/grids/hexagons/).
+    pub fn r(&self) -> i64 {
+        self.r
+    }
+
+    /// Returns the *s* coordinate of the hex in the cubical coordinate system described [here](https://www.redblobgames.com/grids/hexagons/).
+    pub fn s(&self) -> i64 {
+        -(self.q + self.r)
+    }
+
+    // constructor
+    /// Creates a `HexCoord64` with the given *q* and *r* coordinates; *s* is implied, as for `HexCoord::new`.
+    pub fn new(q: i64, r: i64) -> HexCoord64 {
+        HexCoord64 {
+            q,
+            r,
+        }
+    }
+
+    // constants
+    /// A hex coordinate representing the origin of the hex coordinate system.
+    pub const ZERO: HexCoord64 = HexCoord64{q: 0, r: 0};
+
+    /// The largest magnitude of *q*, *r*, or *s* for which SturdyHex guarantees exact results, as for `HexCoord::MAX_COORDINATE`.
+    /// `HexChunker64` conversions are exact within these bounds for any chunk radius.
+    /// Conversion to `HexCoordFraction<f64>` is only exact for coordinates up to 2^53.
+    pub const MAX_COORDINATE: i64 = (1 << 60) - 1;
+
+    // methods
+    /// Returns one of the six unit directions, with the same indexing as `HexCoord::get_unit_coord`.
+    pub fn get_unit_coord<D: Into<HexDirection>>(i: D) -> HexCoord64 {
+        HexCoord::get_unit_coord(i).into()
+    }
+
+    /// Returns the neighbor of `self` in direction `i`, as for `HexCoord::get_neighbor`.
+    pub fn get_neighbor<D: Into<HexDirection>>(&self, i: D) -> HexCoord64 {
+        *self + Self::get_unit_coord(i)
+    }
+
+    /// Returns an iterator over the neighbors of `self`, in direction order.
+    pub fn neighbors(&self) -> impl Iterator<Item = HexCoord64> {
+        let center = *self;
+        (0..6).map(move |i| center.get_neighbor(i))
+    }
+
+    /// Returns the `i`th diagonal hex of `self`, with the same indexing as `HexCoord::get_diagonal`.
+    pub fn get_diagonal<C: Into<HexCorner>>(&self, i: C) -> HexCoord64 {
+        *self + HexCoord::get_diagonal_coord(i).into()
+    }
+
+    /// Returns an iterator over the diagonal hexes of `self`, in corner order.
+    pub fn diagonals(&self) -> impl Iterator<Item = HexCoord64> {
+        let center = *self;
+        (0..6).map(move |i| center.get_diagonal(i))
+    }
+
+    /// Returns the `i`th vertex of `self`, with the same indexing as `HexCoord::get_vertex`.
+    pub fn get_vertex<C: Into<HexCorner>>(&self, i: C) -> HexVertex64 {
+        HexVertex64::get_unit_coord(i).translate(self)
+    }
+
+    /// Returns an iterator over the vertices of `self`, in corner order.
+    pub fn vertices(&self) -> impl Iterator<Item = HexVertex64> {
+        let center = *self;
+        (0..6).map(move |i| center.get_vertex(i))
+    }
+
+    /// Returns the `i`th half-edge of `self`, with the same indexing as `HexCoord::get_half_edge`.
+    pub fn get_half_edge<C: Into<HexCorner>>(&self, i: C) -> HexHalfEdge64 {
+        let i = i.into();
+        HexHalfEdge64::new(
+            self.get_vertex(i),
+            self.get_vertex(i+1),
+        )
+    }
+
+    /// Returns an iterator over the half-edges of `self`, in corner order.
+    pub fn edges(&self) -> impl Iterator<Item = HexHalfEdge64> {
+        let center = *self;
+        (0..6).map(move |i| center.get_half_edge(i))
+    }
+
+    /// Returns a hex coordinate representing `self` rotated around `pivot` by `rotation`, as for `HexCoord::rotate_around`.
+    pub fn rotate_around<R: Into<HexDirection>>(&self, pivot: HexCoord64, rotation: R) -> HexCoord64 {
+        let relative = *self - pivot;
+
+        let relative = match rotation.into().index() {
+            0 => HexCoord64::new(relative.q(), relative.r()),
+            1 => HexCoord64::new(-relative.r(), relative.q() + relative.r()),
+            2 => HexCoord64::new(-relative.q() - relative.r(), relative.q()),
+            3 => HexCoord64::new(-relative.q(), -relative.r()),
+            4 => HexCoord64::new(relative.r(), -relative.q() - relative.r()),
+            5 => HexCoord64::new(relative.q() + relative.r(), -relative.q()),
+            _ => panic!("logic error; rem_euclid(6) should return one of the previous cases"),
+        };
+
+        pivot + relative
+    }
+
+    /// Returns a hex coordinate representing `self` reflected across the line through the origin and hex *(2, -1, -1)*.
+    pub fn reflect(&self) -> HexCoord64 {
+        HexCoord64 {
+            q: self.q(),
+            r: self.s(),
+        }
+    }
+
+    /// Returns the number of hexes in the shortest path along the hex grid from `a` to `b`, as for `HexCoord::hex_distance`.
+    pub fn hex_distance(a: HexCoord64, b: HexCoord64) -> i64 {
+        ((a.q() - b.q()).abs() + (a.r() - b.r()).abs() + (a.s() - b.s()).abs()) / 2
+    }
+
+    /// Returns the number of moves in the shortest path from `a` to `b` when each move may go to a neighbor or to a diagonal, as for `HexCoord::diagonal_distance`.
+    pub fn diagonal_distance(a: HexCoord64, b: HexCoord64) -> i64 {
+        let offset = a - b;
+        let extent = (offset.q() - offset.r()).abs()
+            .max((offset.r() - offset.s()).abs())
+            .max((offset.s() - offset.q()).abs());
+        (extent + 2) / 3
+    }
+
+    /// Returns whether the *q*, *r*, and *s* coordinates of `self` are all within `MAX_COORDINATE` of zero.
+    pub fn is_in_bounds(&self) -> bool {
+        HexCoord64::checked_from_i128(self.q() as i128, self.r() as i128).is_some()
+    }
+
+    /// Returns `self + other`, or `None` if the result is outside the bounds given by `MAX_COORDINATE`.
+    pub fn checked_add(&self, other: HexCoord64) -> Option<HexCoord64> {
+        HexCoord64::checked_from_i128(self.q() as i128 + other.q() as i128, self.r() as i128 + other.r() as i128)
+    }
+
+    /// Returns `self - other`, or `None` if the result is outside the bounds given by `MAX_COORDINATE`.
+    pub fn checked_sub(&self, other: HexCoord64) -> Option<HexCoord64> {
+        HexCoord64::checked_from_i128(self.q() as i128 - other.q() as i128, self.r() as i128 - other.r() as i128)
+    }
+
+    /// Returns `self * scale`, or `None` if the result is outside the bounds given by `MAX_COORDINATE`.
+    pub fn checked_mul(&self, scale: i64) -> Option<HexCoord64> {
+        HexCoord64::checked_from_i128(self.q() as i128 * scale as i128, self.r() as i128 * scale as i128)
+    }
+
+    fn checked_from_i128(q: i128, r: i128) -> Option<HexCoord64> {
+        let max = HexCoord64::MAX_COORDINATE as i128;
+        if q.abs() > max || r.abs() > max || (q + r).abs() > max {
+            return None;
+        }
+
+        Some(HexCoord64::new(q as i64, r as i64))
+    }
+}
+
+// operator overloads
+impl ops::Add for HexCoord64 {
+    type Output = HexCoord64;
+
+    fn add(self, other: HexCoord64) -> HexCoord64 {
+        HexCoord64 {
+            q: self.q() + other.q(),
+            r: self.r() + other.r(),
+        }
+    }
+}
+
+impl ops::AddAssign for HexCoord64 {
+    fn add_assign(&mut self, other: HexCoord64) {
+        *self = *self + other;
+    }
+}
+
+impl ops::Sub for HexCoord64 {
+    type Output = HexCoord64;
+
+    fn sub(self, other: HexCoord64) -> HexCoord64 {
+        HexCoord64 {
+            q: self.q() - other.q(),
+            r: self.r() - other.r(),
+        }
+    }
+}
+
+impl ops::SubAssign for HexCoord64 {
+    fn sub_assign(&mut self, other: HexCoord64) {
+        *self = *self - other;
+    }
+}
+
+impl ops::Neg for HexCoord64 {
+    type Output = HexCoord64;
+
+    fn neg(self) -> HexCoord64 {
+        HexCoord64 {
+            q: -self.q(),
+            r: -self.r(),
+        }
+    }
+}
+
+impl ops::Mul::<i64> for HexCoord64 {
+    type Output = HexCoord64;
+
+    fn mul(self, other: i64) -> HexCoord64 {
+        HexCoord64 {
+            q: self.q * other,
+            r: self.r * other,
+        }
+    }
+}
+
+impl ops::MulAssign::<i64> for HexCoord64 {
+    fn mul_assign(&mut self, other: i64) {
+        *self = *self * other;
+    }
+}
+
+impl fmt::Display for HexCoord64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.q(), self.r(), self.s())
+    }
+}
+
+impl From<HexCoord> for HexCoord64 {
+    fn from(item: HexCoord) -> HexCoord64 {
+        HexCoord64::new(item.q() as i64, item.r() as i64)
+    }
+}
+
+/// An error from converting a 64-bit hex type to its 32-bit counterpart when the coordinates are outside `HexCoord::MAX_COORDINATE`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct HexOutOfBoundsError;
+
+impl fmt::Display for HexOutOfBoundsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "hex coordinates outside the bounds of the 32-bit hex types")
+    }
+}
+
+impl std::error::Error for HexOutOfBoundsError {}
+
+/// Fails unless every coordinate is within `HexCoord::MAX_COORDINATE`.
+impl TryFrom<HexCoord64> for HexCoord {
+    type Error = HexOutOfBoundsError;
+
+    fn try_from(item: HexCoord64) -> Result<HexCoord, HexOutOfBoundsError> {
+        let max = HexCoord::MAX_COORDINATE as i64;
+        if item.q().abs() > max || item.r().abs() > max || item.s().abs() > max {
+            return Err(HexOutOfBoundsError);
+        }
+
+        Ok(HexCoord::new(item.q() as i32, item.r() as i32))
+    }
+}
+
+impl<F: HexFloat> From<HexCoord64> for HexCoordFraction<F> {
+    fn from(item: HexCoord64) -> HexCoordFraction<F> {
+        HexCoordFraction::new(F::from_f64(item.q() as f64), F::from_f64(item.r() as f64))
+    }
+}
+
+/// A vertex of a hex on a hex grid, like `HexVertex` but with 64-bit coordinates.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct HexVertex64 {
+    three_q: i64,
+    three_r: i64,
+}
+
+impl HexVertex64 {
+    // accessors
+    /// Returns three times the *q* coordinate of the vertex, which is always an integer.
+    pub fn three_q(&self) -> i64 {
+        self.three_q
+    }
+
+    /// Returns three times the *r* coordinate of the vertex, which is always an integer.
+    pub fn three_r(&self) -> i64 {
+        self.three_r
+    }
+
+    /// Returns three times the *s* coordinate of the vertex, which is always an integer.
+    pub fn three_s(&self) -> i64 {
+        -(self.three_q + self.three_r)
+    }
+
+    // constructors
+    /// Creates a `HexVertex64` from three times its *q* and *r* coordinates, as for `HexVertex::new`.
+    pub fn new(three_q: i64, three_r: i64) -> HexVertex64 {
+        HexVertex64 {
+            three_q,
+            three_r,
+        }
+    }
+
+    // other methods
+    /// Returns whether `self` is a vertex like corner 0 of a hex, with edges toward the even corners.
+    pub fn on_positive_basis(&self) -> bool {
+        (self.three_q() + 1).rem_euclid(3) == 0 && (self.three_r() + 1).rem_euclid(3) == 0
+    }
+
+    /// Returns whether `self` is a vertex like corner 1 of a hex, with edges toward the odd corners.
+    pub fn on_negative_basis(&self) -> bool {
+        (self.three_q() - 1).rem_euclid(3) == 0 && (self.three_r() - 1).rem_euclid(3) == 0
+    }
+
+    /// Returns outgoing edge `i` of `self`, as for `HexVertex::get_outgoing_edge`.
+    pub fn get_outgoing_edge(&self, i: i32) -> HexHalfEdge64 {
+        let direction =
+            if self.on_positive_basis() {
+                HexVertex64::get_unit_coord(2*i)
+            } else if self.on_negative_basis() {
+                HexVertex64::get_unit_coord(2*i + 1)
+            } else {
+                panic!("HexVertex64 is not on a vertex");
+            };
+
+        self.edge_along(direction)
+    }
+
+    /// Returns the twin of outgoing edge `i` of `self`, as for `HexVertex::get_incoming_edge`.
+    pub fn get_incoming_edge(&self, i: i32) -> HexHalfEdge64 {
+        self.get_outgoing_edge(i).twin()
+    }
+
+    /// Returns the edge leaving `self` toward `corner`, as for `HexVertex::get_outgoing_edge_toward`.
+    pub fn get_outgoing_edge_toward<C: Into<HexCorner>>(&self, corner: C) -> HexHalfEdge64 {
+        // vertices on the positive basis have edges toward the even corners, and those on the negative basis toward the odd ones
+        let corner = corner.into();
+        let direction =
+            if self.on_positive_basis() {
+                HexVertex64::get_unit_coord(corner - corner.index() % 2)
+            } else if self.on_negative_basis() {
+                HexVertex64::get_unit_coord(corner - (corner.index() + 1) % 2)
+            } else {
+                panic!("HexVertex64 is not on a vertex");
+            };
+
+        self.edge_along(direction)
+    }
+
+    /// Returns the edge arriving at `self` from `corner`, the twin of `get_outgoing_edge_toward(corner)`.
+    pub fn get_incoming_edge_from<C: Into<HexCorner>>(&self, corner: C) -> HexHalfEdge64 {
+        self.get_outgoing_edge_toward(corner).twin()
+    }
+
+    fn edge_along(&self, direction: HexVertex64) -> HexHalfEdge64 {
+        HexHalfEdge64::new(
+            *self,
+            HexVertex64::new(
+                self.three_q() + direction.three_q(),
+                self.three_r() + direction.three_r(),
+            )
+        )
+    }
+
+    /// Returns the offset from a hex's center to its corner `i`, as for `HexVertex::get_unit_coord`.
+    pub fn get_unit_coord<C: Into<HexCorner>>(i: C) -> HexVertex64 {
+        HexVertex::get_unit_coord(i).into()
+    }
+
+    /// Returns the diagonal hex of `hex` that lies beyond `self`, or `None` if `self` isn't a vertex of `hex`, as for `HexVertex::diagonal_beyond`.
+    pub fn diagonal_beyond(&self, hex: HexCoord64) -> Option<HexCoord64> {
+        HexCorner::iter()
+            .find(|&corner| hex.get_vertex(corner) == *self)
+            .map(|corner| hex.get_diagonal(corner))
+    }
+
+    /// Returns `self` moved by the offset from the origin to `translation`.
+    pub fn translate(&self, translation: &HexCoord64) -> HexVertex64 {
+        HexVertex64::new(
+            self.three_q() + 3 * translation.q(),
+            self.three_r() + 3 * translation.r(),
+        )
+    }
+}
+
+impl From<HexVertex> for HexVertex64 {
+    fn from(item: HexVertex) -> HexVertex64 {
+        HexVertex64::new(item.three_q() as i64, item.three_r() as i64)
+    }
+}
+
+/// Fails unless every coordinate is within `HexCoord::MAX_COORDINATE + 2/3`, the extent of the vertices of hexes in bounds.
+impl TryFrom<HexVertex64> for HexVertex {
+    type Error = HexOutOfBoundsError;
+
+    fn try_from(item: HexVertex64) -> Result<HexVertex, HexOutOfBoundsError> {
+        let max = 3 * HexCoord::MAX_COORDINATE as i64 + 2;
+        if item.three_q().abs() > max || item.three_r().abs() > max || item.three_s().abs() > max {
+            return Err(HexOutOfBoundsError);
+        }
+
+        Ok(HexVertex::new(item.three_q() as i32, item.three_r() as i32))
+    }
+}
+
+impl<F: HexFloat> From<HexVertex64> for HexCoordFraction<F> {
+    fn from(item: HexVertex64) -> HexCoordFraction<F> {
+        HexCoordFraction::new(
+            F::from_f64(item.three_q() as f64) / F::from_f64(3.0),
+            F::from_f64(item.three_r() as f64) / F::from_f64(3.0),
+        )
+    }
+}
+
+/// A directed edge of a hex on a hex grid, like `HexHalfEdge` but with 64-bit coordinates.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct HexHalfEdge64 {
+    source: HexVertex64,
+    destination: HexVertex64,
+}
+
+impl HexHalfEdge64 {
+    // accessors
+    /// Returns the vertex `self` starts from.
+    pub fn source(&self) -> HexVertex64 {
+        self.source
+    }
+
+    /// Returns the vertex `self` ends at.
+    pub fn destination(&self) -> HexVertex64 {
+        self.destination
+    }
+
+    // constructor
+    /// Creates a half-edge from `source` to `destination`, which should be adjacent vertices.
+    pub fn new(source: HexVertex64, destination: HexVertex64) -> HexHalfEdge64 {
+        HexHalfEdge64 {
+            source,
+            destination,
+        }
+    }
+
+    // methods
+    /// Returns the hex to the left of `self`, as for `HexHalfEdge::hex`.
+    pub fn hex(&self) -> HexCoord64 {
+        // rotate the displacement so it points from source to center instead of source to destination
+        let (dq, dr) = self.displacement();
+        let center_q = self.source.three_q() - dr;
+        let center_r = self.source.three_r() + dq + dr;
+
+        HexCoord64::new(
+            center_q / 3,
+            center_r / 3,
+        )
+    }
+
+    /// Returns the half-edge running the other way along the same edge, which belongs to the neighboring hex.
+    pub fn twin(&self) -> HexHalfEdge64 {
+        HexHalfEdge64 {
+            source: self.destination(),
+            destination: self.source(),
+        }
+    }
+
+    /// Returns the half-edge after `self` around the same hex.
+    pub fn next(&self) -> HexHalfEdge64 {
+        // rotate the displacement so it gives the displacement for the next edge
+        let (dq, dr) = self.displacement();
+        HexHalfEdge64 {
+            source: self.destination(),
+            destination: HexVertex64::new(
+                self.destination.three_q() - dr,
+                self.destination.three_r() + dq + dr,
+            ),
+        }
+    }
+
+    /// Returns the half-edge before `self` around the same hex.
+    pub fn prev(&self) -> HexHalfEdge64 {
+        // rotate the displacement so it gives the displacement for the previous edge
+        let (dq, dr) = self.displacement();
+        HexHalfEdge64 {
+            source: HexVertex64::new(
+                self.source.three_q() - (dq + dr),
+                self.source.three_r() + dq,
+            ),
+            destination: self.source(),
+        }
+    }
+
+    /// Returns the hexes that touch only the source and only the destination of `self`, respectively, as for `HexHalfEdge::diagonal_hexes`.
+    pub fn diagonal_hexes(&self) -> (HexCoord64, HexCoord64) {
+        (self.prev().twin().hex(), self.next().twin().hex())
+    }
+
+    /// Returns `self` moved by the offset from the origin to `translation`.
+    pub fn translate(&self, translation: &HexCoord64) -> HexHalfEdge64 {
+        HexHalfEdge64::new(
+            self.source.translate(translation),
+            self.destination.translate(translation),
+        )
+    }
+
+    fn displacement(&self) -> (i64, i64) {
+        (
+            self.destination.three_q() - self.source.three_q(),
+            self.destination.three_r() - self.source.three_r(),
+        )
+    }
+}
+
+impl From<HexHalfEdge> for HexHalfEdge64 {
+    fn from(item: HexHalfEdge) -> HexHalfEdge64 {
+        HexHalfEdge64::new(item.source().into(), item.destination().into())
+    }
+}
+
+/// Fails unless both vertices convert.
+impl TryFrom<HexHalfEdge64> for HexHalfEdge {
+    type Error = HexOutOfBoundsError;
+
+    fn try_from(item: HexHalfEdge64) -> Result<HexHalfEdge, HexOutOfBoundsError> {
+        Ok(HexHalfEdge::new(item.source().try_into()?, item.destination().try_into()?))
+    }
+}
+
+/// Groups hexes into hexagonal chunks, like `HexChunker` but with 64-bit coordinates.
+pub struct HexChunker64 {
+    chunk_radius: i128,
+    area: i128,
+    shift: i128,
+}
+
+impl HexChunker64 {
+    /// Creates a chunker whose chunks are hexagons of radius `chunk_radius`, as for `HexChunker::new`.
+    // computed in i128, since the area of a chunk and `shift` times a coordinate can overflow an i64
+    pub fn new(chunk_radius: u32) -> HexChunker64 {
+        let chunk_radius = chunk_radius as i128;
+        HexChunker64 {
+            chunk_radius,
+            area: 3 * chunk_radius * chunk_radius + 3 * chunk_radius + 1,
+            shift: 3 * chunk_radius + 2,
+        }
+    }
+
+    /// Returns the hex at the center of the chunk with coordinates `chunk_coord`.
+    pub fn get_chunk_center(&self, chunk_coord: HexCoord64) -> HexCoord64 {
+        let (q, r) = (chunk_coord.q() as i128, chunk_coord.r() as i128);
+        HexCoord64::new(
+            ((2 * self.chunk_radius + 1) * q + self.chunk_radius * r) as i64,
+            (self.chunk_radius * -q + (self.chunk_radius + 1) * r) as i64,
+        )
+    }
+
+    /// Returns the coordinates of the chunk containing `coord`.
+    // same algorithm as HexChunker::get_containing_chunk
+    pub fn get_containing_chunk(&self, coord: HexCoord64) -> HexCoord64 {
+        let (q, r, s) = (coord.q() as i128, coord.r() as i128, coord.s() as i128);
+        let xh = (r + self.shift * q).div_euclid(self.area);
+        let yh = (s + self.shift * r).div_euclid(self.area);
+        let zh = (q + self.shift * s).div_euclid(self.area);
+
+        HexCoord64::new(
+            (1 + xh - yh).div_euclid(3) as i64,
+            (1 + yh - zh).div_euclid(3) as i64,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn matches_32_bit_algorithms() {
+        let chunker = HexChunker::new(3);
+        let chunker64 = HexChunker64::new(3);
+        for &hex in &HexShape::new_hexagon(12) {
+            let wide = HexCoord64::from(hex);
+            assert_eq!(HexCoord::try_from(wide), Ok(hex));
+            assert_eq!(HexCoord64::from(hex.rotate_around(HexCoord::new(2, -1), 4)), wide.rotate_around(HexCoord64::new(2, -1), 4));
+            assert_eq!(HexCoord64::from(chunker.get_containing_chunk(hex)), chunker64.get_containing_chunk(wide));
+            assert_eq!(HexCoord64::from(chunker.get_chunk_center(hex)), chunker64.get_chunk_center(wide));
+            assert!(hex.diagonals().map(HexCoord64::from).eq(wide.diagonals()));
+
+            for i in 0..6 {
+                let edge = hex.get_half_edge(i);
+                let wide_edge = wide.get_half_edge(i);
+                assert_eq!(HexHalfEdge64::from(edge), wide_edge);
+                assert_eq!(HexHalfEdge64::from(edge.next()), wide_edge.next());
+                assert_eq!(HexHalfEdge64::from(edge.prev()), wide_edge.prev());
+                assert_eq!(wide_edge.hex(), wide);
+                assert_eq!(wide_edge.twin().hex(), wide.get_neighbor(i + 1));
+                assert_eq!(HexHalfEdge64::from(edge.source().get_outgoing_edge(i)), wide_edge.source().get_outgoing_edge(i));
+                assert_eq!(HexHalfEdge64::from(edge.source().get_outgoing_edge_toward(i)), wide_edge.source().get_outgoing_edge_toward(i));
+            }
+        }
+    }
+
+    #[test]
+    fn beyond_32_bits() {
+        let far = HexCoord64::new(1 << 40, -(3 << 40));
+        assert_eq!(HexCoord::try_from(far), Err(HexOutOfBoundsError));
+        assert!(HexCoord::try_from(HexCoord64::new(i32::MAX as i64, i32::MAX as i64)).is_err());
+        assert!(HexVertex::try_from(HexVertex64::new(i32::MAX as i64, i32::MAX as i64)).is_err());
+        let max = HexCoord::MAX_COORDINATE;
+        assert_eq!(HexCoord::try_from(HexCoord64::new(max as i64, -(max as i64))), Ok(HexCoord::new(max, -max)));
+        let corner = HexCoord::new(-max, max).get_vertex(2);
+        assert_eq!(HexVertex::try_from(HexVertex64::from(corner)), Ok(corner));
+        assert_eq!(HexCoord64::hex_distance(far, HexCoord64::ZERO), 3 << 40);
+
+        let edge = far.get_half_edge(2);
+        assert_eq!(edge.hex(), far);
+        assert_eq!(edge.next().next().next().next().next().next(), edge);
+        assert!(HexHalfEdge::try_from(edge).is_err());
+        let (source_side, destination_side) = edge.diagonal_hexes();
+        assert_eq!((source_side, source_side.get_diagonal(4)), (far.get_neighbor(2), destination_side));
+        assert_eq!(far.get_vertex(2).diagonal_beyond(far), Some(far.get_diagonal(2)));
+        assert_eq!(far.get_vertex(2).diagonal_beyond(far.get_diagonal(5)), None);
+        assert_eq!(HexCoord64::diagonal_distance(far, far + HexCoord64::new(3 << 40, 0)), 2 << 40);
+
+        let chunker = HexChunker64::new(5);
+        let chunk = chunker.get_containing_chunk(far);
+        assert!(HexCoord64::hex_distance(chunker.get_chunk_center(chunk), far) <= 5);
+
+        let max = HexCoord64::MAX_COORDINATE;
+        assert_eq!(HexCoord64::new(max, 0).checked_add(HexCoord64::new(1, -1)), None);
+        assert_eq!(HexCoord64::new(i64::MAX, 0).checked_mul(2), None);
+
+        // operations stay exact at the bounds, including chunking with the largest radius
+        let extremes = [(max, 0), (-max, 0), (0, max), (0, -max), (max, -max), (-max, max)].map(|(q, r)| HexCoord64::new(q, r));
+        for a in extremes {
+            for b in extremes {
+                let (dq, dr) = (a.q() as i128 - b.q() as i128, a.r() as i128 - b.r() as i128);
+                assert_eq!(HexCoord64::hex_distance(a, b) as i128, (dq.abs() + dr.abs() + (dq + dr).abs()) / 2);
+                assert_eq!(a + b - b, a);
+                for rotation in 0..6 {
+                    assert_eq!(a.rotate_around(b, rotation).rotate_around(b, -rotation), a);
+                }
+            }
+            for corner in HexCorner::iter() {
+                assert_eq!(a.get_half_edge(corner).hex(), a);
+            }
+            for radius in [1, 5, u32::MAX] {
+                let chunker = HexChunker64::new(radius);
+                let center = chunker.get_chunk_center(chunker.get_containing_chunk(a));
+                assert!(HexCoord64::hex_distance(a, center) <= radius as i64);
+            }
+        }
+
+        let position: HexCoordFraction<f64> = far.into();
+        assert_eq!(position.round64(), far);
+    }
+}
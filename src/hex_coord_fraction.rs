@@ -1,28 +1,31 @@
 use std::ops;
 use crate::*;
 
+/// A point on a hex grid, in fractional hex coordinates.
+/// The scalar type `F` defaults to `f32`; use `f64` for extra precision far from the origin.
 #[derive(Copy, Clone, Debug)]
-pub struct HexCoordFraction {
-    q: f32,
-    r: f32
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HexCoordFraction<F: HexFloat = f32> {
+    q: F,
+    r: F,
 }
 
-impl HexCoordFraction {
+impl<F: HexFloat> HexCoordFraction<F> {
     // accessors
-    pub fn q(&self) -> f32 {
+    pub fn q(&self) -> F {
         self.q
     }
 
-    pub fn r(&self) -> f32 {
+    pub fn r(&self) -> F {
         self.r
     }
 
-    pub fn s(&self) -> f32 {
+    pub fn s(&self) -> F {
         -(self.q + self.r)
     }
 
     // constructor
-    pub fn new(q: f32, r: f32) -> HexCoordFraction {
+    pub fn new(q: F, r: F) -> HexCoordFraction<F> {
         HexCoordFraction {
             q,
             r,
@@ -30,26 +33,36 @@ impl HexCoordFraction {
     }
 
     // constants
-    pub const ZERO: HexCoordFraction = HexCoordFraction {q: 0.0, r: 0.0};
+    pub const ZERO: HexCoordFraction<F> = HexCoordFraction {q: F::ZERO, r: F::ZERO};
 
     // methods
-    pub fn get_unit_coord(i: i32) -> HexCoordFraction {
-        match i.rem_euclid(6) {
-            0 => HexCoordFraction::new(0.0, -1.0),
-            1 => HexCoordFraction::new(0.0, 1.0),
-            2 => HexCoordFraction::new(-1.0, 0.0),
-            3 => HexCoordFraction::new(1.0, 0.0),
-            4 => HexCoordFraction::new(-1.0, 1.0),
-            5 => HexCoordFraction::new(1.0, -1.0),
+    pub fn get_unit_coord(i: i32) -> HexCoordFraction<F> {
+        let (q, r) = match i.rem_euclid(6) {
+            0 => (0.0, -1.0),
+            1 => (0.0, 1.0),
+            2 => (-1.0, 0.0),
+            3 => (1.0, 0.0),
+            4 => (-1.0, 1.0),
+            5 => (1.0, -1.0),
             _ => panic!("logic error; rem_euclid(6) should return one of the previous cases"),
+        };
+        HexCoordFraction::new(F::from_f64(q), F::from_f64(r))
+    }
+
+    /// Converts `self` to use the scalar type `G`, rounding to the nearest representable value.
+    pub fn cast<G: HexFloat>(&self) -> HexCoordFraction<G> {
+        HexCoordFraction {
+            q: G::from_f64(self.q.as_f64()),
+            r: G::from_f64(self.r.as_f64()),
         }
     }
 
-    pub fn rotate_around(&self, pivot: HexCoordFraction, rotation: i32) -> HexCoordFraction {
+    /// Returns `self` rotated around `pivot` by `rotation`, as for `HexCoord::rotate_around`.
+    pub fn rotate_around<R: Into<HexDirection>>(&self, pivot: HexCoordFraction<F>, rotation: R) -> HexCoordFraction<F> {
         let relative = *self - pivot;
         
         // mathematica: Table[{{0,-1},{1,1}}^n.{q,r},{n,0,5}]
-        let relative = match rotation.rem_euclid(6) {
+        let relative = match rotation.into().index() {
             0 => HexCoordFraction::new(relative.q(), relative.r()),
             1 => HexCoordFraction::new(-relative.r(), relative.q() + relative.r()),
             2 => HexCoordFraction::new(-relative.q() - relative.r(), relative.q()),
@@ -62,11 +75,113 @@ impl HexCoordFraction {
         pivot + relative
     }
 
-    pub fn hex_distance(a: HexCoordFraction, b: HexCoordFraction) -> f32 {
-        ((a.q() - b.q()).abs() + (a.r() - b.r()).abs() + (a.s() - b.s()).abs()) / 2.0
+    pub fn hex_distance(a: HexCoordFraction<F>, b: HexCoordFraction<F>) -> F {
+        ((a.q() - b.q()).abs() + (a.r() - b.r()).abs() + (a.s() - b.s()).abs()) / F::from_f64(2.0)
+    }
+
+    /// Returns the straight-line distance between `a` and `b`, in units of the distance from a hex's center to its vertices.
+    pub fn euclidean_distance(a: HexCoordFraction<F>, b: HexCoordFraction<F>) -> F {
+        let (x, y) = (a - b).cartesian();
+        x.hypot(y)
+    }
+
+    /// Returns the vertex nearest to `self`, along with the `euclidean_distance` to it.
+    pub fn round_to_vertex(&self) -> (HexVertex, F) {
+        self.round()
+            .vertices()
+            .map(|vertex| (vertex, HexCoordFraction::euclidean_distance(*self, vertex.into())))
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .unwrap()
+    }
+
+    /// Returns the edge nearest to `self`, along with the `euclidean_distance` to the nearest point on it.
+    /// The edge is returned as the half-edge belonging to the hex containing `self`.
+    pub fn round_to_edge(&self) -> (HexHalfEdge, F) {
+        self.round()
+            .edges()
+            .map(|edge| (edge, self.distance_to_segment(edge.source().into(), edge.destination().into())))
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .unwrap()
+    }
+
+    /// Divides the hex containing `self` into six triangles, each made of the hex's center and one of its edges, and locates `self` within them.
+    /// Returns the containing hex, the corner `i` starting the triangle containing `self`, and the barycentric coordinates of `self` within it.
+    /// Triangle `i` lies under `get_half_edge(i)`, with corners at the hex's center, `get_vertex(i)`, and `get_vertex(i + 1)`, in the same order as the barycentric coordinates.
+    pub fn sector(&self) -> (HexCoord, HexCorner, [F; 3]) {
+        let hex = self.round();
+        let local = *self - hex.into();
+
+        HexCorner::iter()
+            .map(|i| {
+                let a: HexCoordFraction<F> = HexVertex::get_unit_coord(i).into();
+                let b: HexCoordFraction<F> = HexVertex::get_unit_coord(i + 1).into();
+
+                // solve local = weight_a * a + weight_b * b
+                let determinant = a.q() * b.r() - a.r() * b.q();
+                let weight_a = (local.q() * b.r() - local.r() * b.q()) / determinant;
+                let weight_b = (a.q() * local.r() - a.r() * local.q()) / determinant;
+
+                (i, [F::one() - weight_a - weight_b, weight_a, weight_b])
+            })
+            .max_by(|x, y| {
+                // the containing triangle is the one where neither edge weight is negative
+                let x_min = x.1[1].min(x.1[2]);
+                let y_min = y.1[1].min(y.1[2]);
+                x_min.partial_cmp(&y_min).unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .map(|(i, barycentric)| (hex, i, barycentric))
+            .unwrap()
+    }
+
+    // cartesian coordinates with a flat-top layout, in units of the distance from a hex's center to its vertices
+    fn cartesian(&self) -> (F, F) {
+        (
+            F::from_f64(1.5) * self.q(),
+            F::from_f64(3.0).sqrt() * (self.r() + self.q() / F::from_f64(2.0)),
+        )
+    }
+
+    fn distance_to_segment(&self, a: HexCoordFraction<F>, b: HexCoordFraction<F>) -> F {
+        let (px, py) = (*self - a).cartesian();
+        let (dx, dy) = (b - a).cartesian();
+
+        let t = ((px * dx + py * dy) / (dx * dx + dy * dy)).max(F::zero()).min(F::one());
+        (px - t * dx).hypot(py - t * dy)
     }
 
+    /// Returns the hex containing `self`.
+    /// Coordinates too large for `i32` saturate, and `NaN` becomes zero; use `try_round` to detect these cases.
     pub fn round(&self) -> HexCoord {
+        let (q_round, r_round) = self.round_components();
+        HexCoord::new(
+            q_round.as_i32(),
+            r_round.as_i32(),
+        )
+    }
+
+    /// Returns the hex containing `self`, or `None` if `self` isn't finite or the hex is outside the bounds given by `HexCoord::MAX_COORDINATE`.
+    pub fn try_round(&self) -> Option<HexCoord> {
+        let (q_round, r_round) = self.round_components();
+        // `max` may round up in F, so check it again after conversion, where it's exact
+        let max = F::from_i32(HexCoord::MAX_COORDINATE);
+        if !(q_round.abs() <= max && r_round.abs() <= max) {
+            return None;
+        }
+
+        Some(HexCoord::new(q_round.as_i32(), r_round.as_i32())).filter(HexCoord::is_in_bounds)
+    }
+
+    /// Returns the hex containing `self` as a `HexCoord64`, for grids too large for `i32`.
+    /// Coordinates too large for `i64` saturate, and `NaN` becomes zero.
+    pub fn round64(&self) -> HexCoord64 {
+        let (q_round, r_round) = self.round_components();
+        HexCoord64::new(
+            q_round.as_i64(),
+            r_round.as_i64(),
+        )
+    }
+
+    fn round_components(&self) -> (F, F) {
         let mut q_round = self.q().round();
         let mut r_round = self.r().round();
         let s_round = self.s().round();
@@ -82,39 +197,42 @@ impl HexCoordFraction {
         }
         // third possibility is irrelevant because we don't use s_round in the final answer
         
-        // todo!("handle float overflow gracefully");
-        HexCoord::new(
-            q_round as i32,
-            r_round as i32,
-        )
+        (q_round, r_round)
     }
 }
 
+
 // into
-impl From<HexCoord> for HexCoordFraction {
-    fn from(item: HexCoord) -> HexCoordFraction {
+impl<F: HexFloat> From<HexCoord> for HexCoordFraction<F> {
+    fn from(item: HexCoord) -> HexCoordFraction<F> {
         HexCoordFraction {
-            q: item.q() as f32,
-            r: item.r() as f32,
+            q: F::from_i32(item.q()),
+            r: F::from_i32(item.r()),
         }
     }
 }
 
-impl From<HexVertex> for HexCoordFraction {
-    fn from(item: HexVertex) -> HexCoordFraction {
+impl<F: HexFloat> From<HexVertex> for HexCoordFraction<F> {
+    fn from(item: HexVertex) -> HexCoordFraction<F> {
         HexCoordFraction {
-            q: item.three_q() as f32 / 3.0,
-            r: item.three_r() as f32 / 3.0,
+            q: F::from_i32(item.three_q()) / F::from_f64(3.0),
+            r: F::from_i32(item.three_r()) / F::from_f64(3.0),
         }
     }
 }
 
+impl From<HexCoordFraction<f32>> for HexCoordFraction<f64> {
+    fn from(item: HexCoordFraction<f32>) -> HexCoordFraction<f64> {
+        item.cast()
+    }
+}
+
 
 // operator overloads
-impl ops::Add for HexCoordFraction {
-    type Output = HexCoordFraction;
+impl<F: HexFloat> ops::Add for HexCoordFraction<F> {
+    type Output = HexCoordFraction<F>;
 
-    fn add(self, other: HexCoordFraction) -> HexCoordFraction {
+    fn add(self, other: HexCoordFraction<F>) -> HexCoordFraction<F> {
         HexCoordFraction {
             q: self.q() + other.q(),
             r: self.r() + other.r(),
@@ -122,16 +240,16 @@ impl ops::Add for HexCoordFraction {
     }
 }
 
-impl ops::AddAssign for HexCoordFraction {
-    fn add_assign(&mut self, other: HexCoordFraction) {
+impl<F: HexFloat> ops::AddAssign for HexCoordFraction<F> {
+    fn add_assign(&mut self, other: HexCoordFraction<F>) {
         *self = *self + other;
     }
 }
 
-impl ops::Sub for HexCoordFraction {
-    type Output = HexCoordFraction;
+impl<F: HexFloat> ops::Sub for HexCoordFraction<F> {
+    type Output = HexCoordFraction<F>;
 
-    fn sub(self, other: HexCoordFraction) -> HexCoordFraction {
+    fn sub(self, other: HexCoordFraction<F>) -> HexCoordFraction<F> {
         HexCoordFraction {
             q: self.q() - other.q(),
             r: self.r() - other.r(),
@@ -139,16 +257,16 @@ impl ops::Sub for HexCoordFraction {
     }
 }
 
-impl ops::SubAssign for HexCoordFraction {
-    fn sub_assign(&mut self, other: HexCoordFraction) {
+impl<F: HexFloat> ops::SubAssign for HexCoordFraction<F> {
+    fn sub_assign(&mut self, other: HexCoordFraction<F>) {
         *self = *self - other;
     }
 }
 
-impl ops::Neg for HexCoordFraction {
-    type Output = HexCoordFraction;
+impl<F: HexFloat> ops::Neg for HexCoordFraction<F> {
+    type Output = HexCoordFraction<F>;
 
-    fn neg(self) -> HexCoordFraction {
+    fn neg(self) -> HexCoordFraction<F> {
         HexCoordFraction{
             q: -self.q(),
             r: -self.r(),
@@ -156,38 +274,38 @@ impl ops::Neg for HexCoordFraction {
     }
 }
 
-impl ops::Mul::<i32> for HexCoordFraction {
-    type Output = HexCoordFraction;
+impl<F: HexFloat> ops::Mul::<i32> for HexCoordFraction<F> {
+    type Output = HexCoordFraction<F>;
 
-    fn mul(self, other: i32) -> HexCoordFraction {
+    fn mul(self, other: i32) -> HexCoordFraction<F> {
         HexCoordFraction {
-            q: self.q * other as f32,
-            r: self.r * other as f32,
+            q: self.q * F::from_i32(other),
+            r: self.r * F::from_i32(other),
         }
     }
 }
 
-impl ops::Mul::<HexCoordFraction> for i32 {
-    type Output = HexCoordFraction;
+impl<F: HexFloat> ops::Mul::<HexCoordFraction<F>> for i32 {
+    type Output = HexCoordFraction<F>;
 
-    fn mul(self, other: HexCoordFraction) -> HexCoordFraction {
+    fn mul(self, other: HexCoordFraction<F>) -> HexCoordFraction<F> {
         HexCoordFraction {
-            q: self as f32 * other.q(),
-            r: self as f32 * other.r(),
+            q: F::from_i32(self) * other.q(),
+            r: F::from_i32(self) * other.r(),
         }
     }
 }
 
-impl ops::MulAssign::<i32> for HexCoordFraction {
+impl<F: HexFloat> ops::MulAssign::<i32> for HexCoordFraction<F> {
     fn mul_assign(&mut self, other: i32) {
         *self = *self * other;
     }
 }
 
-impl ops::Mul::<f32> for HexCoordFraction {
-    type Output = HexCoordFraction;
+impl<F: HexFloat> ops::Mul::<F> for HexCoordFraction<F> {
+    type Output = HexCoordFraction<F>;
 
-    fn mul(self, other: f32) -> HexCoordFraction {
+    fn mul(self, other: F) -> HexCoordFraction<F> {
         HexCoordFraction {
             q: self.q * other,
             r: self.r * other,
@@ -195,19 +313,100 @@ impl ops::Mul::<f32> for HexCoordFraction {
     }
 }
 
-impl ops::Mul::<HexCoordFraction> for f32 {
-    type Output = HexCoordFraction;
+macro_rules! impl_scalar_mul {
+    ($scalar:ty) => {
+        impl ops::Mul::<HexCoordFraction<$scalar>> for $scalar {
+            type Output = HexCoordFraction<$scalar>;
 
-    fn mul(self, other: HexCoordFraction) -> HexCoordFraction {
-        HexCoordFraction {
-            q: self * other.q(),
-            r: self * other.r(),
+            fn mul(self, other: HexCoordFraction<$scalar>) -> HexCoordFraction<$scalar> {
+                HexCoordFraction {
+                    q: self * other.q(),
+                    r: self * other.r(),
+                }
+            }
         }
-    }
+    };
 }
 
-impl ops::MulAssign::<f32> for HexCoordFraction {
-    fn mul_assign(&mut self, other: f32) {
+impl_scalar_mul!(f32);
+impl_scalar_mul!(f64);
+
+impl<F: HexFloat> ops::MulAssign::<F> for HexCoordFraction<F> {
+    fn mul_assign(&mut self, other: F) {
         *self = *self * other;
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn nearest_vertex_and_edge() {
+        let hex = HexCoord::new(3, -1);
+        let center: HexCoordFraction = hex.into();
+        for i in 0..6 {
+            let vertex: HexCoordFraction = hex.get_vertex(i).into();
+            let near_vertex = center + (vertex - center) * 0.8;
+            let (nearest, distance) = near_vertex.round_to_vertex();
+            assert_eq!(nearest, hex.get_vertex(i));
+            assert!((distance - 0.2).abs() < 1e-5);
+
+            let next: HexCoordFraction = hex.get_vertex(i + 1).into();
+            let midpoint = (vertex + next) * 0.5;
+            let near_edge = center + (midpoint - center) * 0.75;
+            let (nearest, distance) = near_edge.round_to_edge();
+            assert_eq!(nearest, hex.get_half_edge(i));
+            assert!((distance - 0.25 * f32::sqrt(3.0) / 2.0).abs() < 1e-5);
+        }
+
+        let neighbor: HexCoordFraction = HexCoord::new(1, 0).into();
+        assert!((HexCoordFraction::euclidean_distance(HexCoordFraction::ZERO, neighbor) - f32::sqrt(3.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn sectors_match_vertex_indexing() {
+        let hex = HexCoord::new(-2, 5);
+        let center: HexCoordFraction = hex.into();
+        for i in 0..6 {
+            let a: HexCoordFraction = hex.get_vertex(i).into();
+            let b: HexCoordFraction = hex.get_vertex(i + 1).into();
+            let point = center * 0.5 + a * 0.3 + b * 0.2;
+
+            let (containing, sector, barycentric) = point.sector();
+            assert_eq!(containing, hex);
+            assert_eq!(sector, HexCorner::new(i));
+            for (weight, expected) in barycentric.iter().zip([0.5, 0.3, 0.2]) {
+                assert!((weight - expected).abs() < 1e-5);
+            }
+        }
+
+        let (_, _, barycentric) = center.sector();
+        assert!((barycentric[0] - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn precision_conversions() {
+        let single: HexCoordFraction = HexCoordFraction::new(1.25, -0.5);
+        let double: HexCoordFraction<f64> = single.into();
+        assert_eq!(double.q(), 1.25);
+        assert_eq!(double.s(), -0.75);
+        assert_eq!(double.cast::<f32>().round(), single.round());
+
+        let far = HexCoordFraction::<f64>::new(123_456_789.4, -23_456_789.3);
+        assert_eq!(far.round(), HexCoord::new(123_456_789, -23_456_789));
+    }
+
+    #[test]
+    fn try_round_rejects_unrepresentable_hexes() {
+        assert_eq!(HexCoordFraction::<f32>::new(1.2, -0.4).try_round(), Some(HexCoord::new(1, 0)));
+        assert_eq!(HexCoordFraction::<f32>::new(f32::NAN, 0.0).try_round(), None);
+        assert_eq!(HexCoordFraction::<f32>::new(f32::INFINITY, 0.0).try_round(), None);
+        assert_eq!(HexCoordFraction::<f32>::new(1e10, 0.0).try_round(), None);
+        assert_eq!(HexCoordFraction::<f32>::new(2f32.powi(28), 0.0).try_round(), None);
+
+        let max = HexCoord::MAX_COORDINATE;
+        assert_eq!(HexCoordFraction::<f64>::new(max as f64, 0.0).try_round(), Some(HexCoord::new(max, 0)));
+        assert_eq!(HexCoordFraction::<f64>::new(max as f64, max as f64).try_round(), None);
+    }
+}
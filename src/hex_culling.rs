@@ -0,0 +1,282 @@
+use crate::*;
+use crate::hex_vector::{HexVector3, Vector2, Vector3};
+
+/// An iterator over the hexes of a `HexField` that may intersect a convex region of its plane.
+/// Created by `HexField::hexes_in_polygon` and `HexField::hexes_in_frustum`.
+///
+/// The iteration is conservative: it returns every hex that intersects the region, along with a few hexes near the region's boundary that don't.
+pub struct HexPolygonIterator<F: HexFloat> {
+    // inward unit normals and offsets of the region's edges, in plane coordinates
+    half_planes: Vec<(Vector2<F>, F)>,
+    q_center: Vector2<F>,
+    r_center: Vector2<F>,
+    outer_radius: F,
+    range: Option<HexRangeIterator>,
+}
+
+impl<F: HexFloat> HexPolygonIterator<F> {
+    fn may_intersect(&self, hex: HexCoord) -> bool {
+        let q = F::from_i32(hex.q());
+        let r = F::from_i32(hex.r());
+        let center = self.q_center * q + self.r_center * r;
+
+        self.half_planes.iter()
+            .all(|(normal, offset)| normal.x * center.x + normal.y * center.y + *offset >= -self.outer_radius)
+    }
+}
+
+impl<F: HexFloat> Iterator for HexPolygonIterator<F> {
+    type Item = HexCoord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let hex = self.range.as_mut()?.next()?;
+            if self.may_intersect(hex) {
+                return Some(hex);
+            }
+        }
+    }
+}
+
+impl<F: HexFloat, V: HexVector3<F>> HexField<F, V> {
+    /// Returns an iterator over the hexes that may intersect a convex polygon on the plane of the hex grid.
+    /// The polygon's vertices are in the local cartesian coordinates of the hex grid, as returned by `project_onto_plane`; their z-coordinates are ignored.
+    /// The vertices may be wound in either direction.
+    pub fn hexes_in_polygon(&self, polygon: &[V]) -> HexPolygonIterator<F> {
+        let points: Vec<Vector2<F>> = polygon.iter()
+            .map(|vertex| {
+                let [x, y, _] = vertex.xyz();
+                Vector2::new(x, y)
+            })
+            .collect();
+
+        self.polygon_iterator(&points)
+    }
+
+    /// Returns an iterator over the hexes that may intersect the region of the hex grid's plane inside a convex volume, such as a camera frustum.
+    /// Each plane is given as a worldspace normal pointing into the volume and a distance, so that a worldspace point `p` is inside the plane when `normal · p + distance >= 0`.
+    /// Returns `None` if the region is unbounded, as when the volume has no far plane.
+    pub fn hexes_in_frustum(&self, planes: &[(V, F)]) -> Option<HexPolygonIterator<F>> {
+        let origin = Vector3::from_hex_vector(self.origin());
+        let x_basis = Vector3::from_hex_vector(self.x_basis());
+        let y_basis = Vector3::from_hex_vector(self.y_basis());
+
+        // restrict each plane to the hex grid's plane, giving a half-plane a * x + b * y + c >= 0
+        let mut lines = Vec::new();
+        for (normal, distance) in planes {
+            let normal = Vector3::from_hex_vector(*normal);
+            let a = normal.dot(x_basis);
+            let b = normal.dot(y_basis);
+            let c = normal.dot(origin) + *distance;
+
+            if (a * a + b * b).sqrt() <= F::epsilon() * normal.magnitude() {
+                // parallel to the grid, so the grid is either entirely inside or entirely outside
+                if c < F::zero() {
+                    return Some(self.polygon_iterator(&[]));
+                }
+            } else {
+                lines.push((a, b, c));
+            }
+        }
+
+        // any vertex of a bounded region is the intersection of two lines, so a square around all of them contains it
+        let mut extent = self.size();
+        for (i, (a_i, b_i, c_i)) in lines.iter().enumerate() {
+            for (a_j, b_j, c_j) in &lines[i + 1..] {
+                let determinant = *a_i * *b_j - *a_j * *b_i;
+                if determinant.abs() > F::epsilon() {
+                    let x = (*b_i * *c_j - *b_j * *c_i) / determinant;
+                    let y = (*a_j * *c_i - *a_i * *c_j) / determinant;
+                    extent = extent.max(x.abs()).max(y.abs());
+                }
+            }
+        }
+        let extent = extent * F::from_f64(2.0);
+
+        let mut region = vec![
+            Vector2::new(-extent, -extent),
+            Vector2::new(extent, -extent),
+            Vector2::new(extent, extent),
+            Vector2::new(-extent, extent),
+        ];
+        for (a, b, c) in &lines {
+            region = Self::clip_polygon(&region, *a, *b, *c);
+        }
+
+        // a region reaching the square's boundary extends past every intersection, so it is unbounded
+        let limit = extent * F::from_f64(0.75);
+        if region.iter().any(|point| point.x.abs() > limit || point.y.abs() > limit) {
+            return None;
+        }
+
+        Some(self.polygon_iterator(&region))
+    }
+
+    fn polygon_iterator(&self, polygon: &[Vector2<F>]) -> HexPolygonIterator<F> {
+        let x_basis = Vector3::from_hex_vector(self.x_basis());
+        let y_basis = Vector3::from_hex_vector(self.y_basis());
+        let origin = Vector3::from_hex_vector(self.origin());
+        let plane_position = |hex: HexCoord| {
+            let position = self.position(hex.into()) - origin;
+            Vector2::new(position.dot(x_basis), position.dot(y_basis))
+        };
+
+        let mut iterator = HexPolygonIterator {
+            half_planes: Vec::new(),
+            q_center: plane_position(HexCoord::new(1, 0)),
+            r_center: plane_position(HexCoord::new(0, 1)),
+            outer_radius: self.size(),
+            range: None,
+        };
+        if polygon.len() < 3 {
+            return iterator;
+        }
+
+        // wind the polygon CCW so the interior is to the left of each edge
+        let twice_area = (0..polygon.len())
+            .map(|i| {
+                let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+                a.x * b.y - b.x * a.y
+            })
+            .fold(F::zero(), |sum, x| sum + x);
+        let winding = if twice_area < F::zero() { -F::one() } else { F::one() };
+
+        for i in 0..polygon.len() {
+            let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+            let direction = (b - a) * winding;
+            let length = (direction.x * direction.x + direction.y * direction.y).sqrt();
+            if length > F::zero() {
+                let normal = Vector2::new(-direction.y, direction.x) / length;
+                iterator.half_planes.push((normal, -(normal.x * a.x + normal.y * a.y)));
+            }
+        }
+
+        // cover the polygon's bounding box with a hexagonal range
+        let mut min = polygon[0];
+        let mut max = polygon[0];
+        for point in polygon {
+            min = Vector2::new(min.x.min(point.x), min.y.min(point.y));
+            max = Vector2::new(max.x.max(point.x), max.y.max(point.y));
+        }
+        let center = (min + max) / F::from_f64(2.0);
+        let half_diagonal = ((max.x - min.x) * (max.x - min.x) + (max.y - min.y) * (max.y - min.y)).sqrt() / F::from_f64(2.0);
+
+        let center_hex = self.hex_coord_fraction(self.position_on_plane(center)).round();
+
+        // hexes n steps from the center hex are at least 1.5 * n * outer_radius from its center
+        let radius = ((half_diagonal + self.size() * F::from_f64(2.0)) / (self.size() * F::from_f64(1.5))).ceil();
+        iterator.range = Some(HexRangeIterator::new(radius.as_i32() as u32, HexTransform::new(center_hex, 0)));
+
+        iterator
+    }
+
+    // worldspace position of a point in plane coordinates
+    fn position_on_plane(&self, point: Vector2<F>) -> Vector3<F> {
+        Vector3::from_hex_vector(self.origin())
+            + Vector3::from_hex_vector(self.x_basis()) * point.x
+            + Vector3::from_hex_vector(self.y_basis()) * point.y
+    }
+
+    // keeps the part of a convex polygon where a * x + b * y + c >= 0
+    fn clip_polygon(polygon: &[Vector2<F>], a: F, b: F, c: F) -> Vec<Vector2<F>> {
+        let side = |point: Vector2<F>| a * point.x + b * point.y + c;
+
+        let mut clipped = Vec::new();
+        for i in 0..polygon.len() {
+            let (current, next) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+            let (current_side, next_side) = (side(current), side(next));
+
+            if current_side >= F::zero() {
+                clipped.push(current);
+            }
+            if (current_side >= F::zero()) != (next_side >= F::zero()) {
+                let t = current_side / (current_side - next_side);
+                clipped.push(current + (next - current) * t);
+            }
+        }
+
+        clipped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::collections::HashSet;
+
+    fn layout(orientation: HexOrientation) -> HexField<f32, [f32; 3]> {
+        HexField::with_layout([1.0, 2.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0], orientation, Handedness::RightHanded, 1.0)
+    }
+
+    // hex centers in plane coordinates
+    fn plane_position(field: &HexField<f32, [f32; 3]>, hex: HexCoord) -> [f32; 2] {
+        let [x, y, _] = field.project_onto_plane(field.get_position(hex));
+        [x, y]
+    }
+
+    #[test]
+    fn polygon_is_covered() {
+        for orientation in [HexOrientation::FlatTop, HexOrientation::PointyTop] {
+            let field = layout(orientation);
+            let triangle = [[-4.0, -3.0, 0.0], [6.0, 0.0, 0.0], [0.0, 5.0, 0.0]];
+
+            let hexes: HashSet<HexCoord> = field.hexes_in_polygon(&triangle).collect();
+            let reversed: HashSet<HexCoord> = field.hexes_in_polygon(&[triangle[2], triangle[1], triangle[0]]).collect();
+            assert_eq!(hexes, reversed);
+
+            for hex in HexRangeIterator::new(12, HexTransform::IDENTITY) {
+                // a hex with a vertex or center inside the triangle intersects it
+                let inside = |[x, y]: [f32; 2]| {
+                    (0..3).all(|i| {
+                        let [ax, ay, _] = triangle[i];
+                        let [bx, by, _] = triangle[(i + 1) % 3];
+                        (bx - ax) * (y - ay) - (by - ay) * (x - ax) >= 0.0
+                    })
+                };
+                let mut points = vec![plane_position(&field, hex)];
+                points.extend((0..6).map(|i| {
+                    let [x, y, _] = field.project_onto_plane(field.get_face_vertex_position(hex, 1.0, 0.0, i));
+                    [x, y]
+                }));
+                if points.into_iter().any(inside) {
+                    assert!(hexes.contains(&hex));
+                }
+            }
+
+            // conservative, but only near the triangle's bounding box
+            assert!(hexes.iter().all(|hex| {
+                let [x, y] = plane_position(&field, *hex);
+                (x - 1.0).abs() < 7.0 && (y - 1.0).abs() < 6.0
+            }));
+        }
+
+        let field = layout(HexOrientation::FlatTop);
+        assert_eq!(field.hexes_in_polygon(&[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0]]).count(), 0);
+    }
+
+    #[test]
+    fn frustum_matches_polygon() {
+        let field = layout(HexOrientation::FlatTop);
+
+        // a box spanning worldspace x in [-4, 6] and y in [-1, 5], which is plane x in [-5, 5] and y in [-3, 3]
+        let sides = [
+            ([1.0, 0.0, 0.0], 4.0),
+            ([-1.0, 0.0, 0.0], 6.0),
+            ([0.0, 1.0, 0.0], 1.0),
+            ([0.0, -1.0, 0.0], 5.0),
+        ];
+        let mut planes = sides.to_vec();
+        planes.push(([0.0, 0.0, 1.0], 1.0));
+        planes.push(([0.0, 0.0, -1.0], 10.0));
+
+        let frustum: HashSet<HexCoord> = field.hexes_in_frustum(&planes).unwrap().collect();
+        let rectangle = [[-5.0, -3.0, 0.0], [5.0, -3.0, 0.0], [5.0, 3.0, 0.0], [-5.0, 3.0, 0.0]];
+        let polygon: HashSet<HexCoord> = field.hexes_in_polygon(&rectangle).collect();
+        assert_eq!(frustum, polygon);
+
+        assert!(field.hexes_in_frustum(&sides[..3]).is_none());
+
+        let above = [([0.0, 0.0, 1.0], -1.0)];
+        assert_eq!(field.hexes_in_frustum(&above).unwrap().count(), 0);
+    }
+}
@@ -14,6 +14,8 @@ pub trait HexData<T> {
     fn remove(&mut self, hex: HexCoord) -> Option<T>;
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct HashMapHexData<T> {
     data: collections::HashMap<HexCoord, T>,
 }
@@ -43,4 +45,11 @@ impl<T> HexData<T> for HashMapHexData<T> {
     }
 }
 
+impl<T> HashMapHexData<T> {
+    /// Returns an iterator over the hexes that have values, with their values, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (HexCoord, &T)> {
+        self.data.iter().map(|(hex, value)| (*hex, value))
+    }
+}
+
 
@@ -0,0 +1,278 @@
+use std::ops;
+use crate::*;
+
+/// One of the six directions from a hex to its neighbors, indexed like `HexCoord::get_unit_coord`.
+/// Directions are numbered CCW in a right-handed `HexField`; rotating by `i` sixth-turns adds `i` to the index.
+///
+/// The `FLAT_` and `POINTY_` constants name the directions as they appear in a right-handed `HexField` with a `FlatTop` or `PointyTop` orientation, looking down from the normal with the y-axis pointing up.
+/// APIs that take a direction also accept an `i32`, wrapped (not clamped) to the range [0, 5].
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HexDirection {
+    Zero,
+    One,
+    Two,
+    Three,
+    Four,
+    Five,
+}
+
+impl HexDirection {
+    // constants
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::Zero,
+        HexDirection::One,
+        HexDirection::Two,
+        HexDirection::Three,
+        HexDirection::Four,
+        HexDirection::Five,
+    ];
+
+    pub const FLAT_SOUTH_EAST: HexDirection = HexDirection::Zero;
+    pub const FLAT_NORTH_EAST: HexDirection = HexDirection::One;
+    pub const FLAT_NORTH: HexDirection = HexDirection::Two;
+    pub const FLAT_NORTH_WEST: HexDirection = HexDirection::Three;
+    pub const FLAT_SOUTH_WEST: HexDirection = HexDirection::Four;
+    pub const FLAT_SOUTH: HexDirection = HexDirection::Five;
+
+    pub const POINTY_EAST: HexDirection = HexDirection::Zero;
+    pub const POINTY_NORTH_EAST: HexDirection = HexDirection::One;
+    pub const POINTY_NORTH_WEST: HexDirection = HexDirection::Two;
+    pub const POINTY_WEST: HexDirection = HexDirection::Three;
+    pub const POINTY_SOUTH_WEST: HexDirection = HexDirection::Four;
+    pub const POINTY_SOUTH_EAST: HexDirection = HexDirection::Five;
+
+    // methods
+    /// Returns the offset from a hex to its neighbor in this direction.
+    pub fn unit_coord(&self) -> HexCoord {
+        HexCoord::get_unit_coord(*self)
+    }
+
+    /// Returns the two corners at the ends of the edge a hex shares with its neighbor in this direction, in CCW order.
+    /// Direction `i` lies between corners `i - 1` and `i`, so the shared edge is half-edge `i - 1`.
+    pub fn corners(&self) -> (HexCorner, HexCorner) {
+        (HexCorner::new(self.index() - 1), HexCorner::new(self.index()))
+    }
+}
+
+/// One of the six corners of a hex, indexed like `HexCoord::get_vertex`.
+/// Corners are numbered CCW in a right-handed `HexField`; rotating by `i` sixth-turns adds `i` to the index.
+///
+/// The `FLAT_` and `POINTY_` constants name the corners as they appear in a right-handed `HexField` with a `FlatTop` or `PointyTop` orientation, looking down from the normal with the y-axis pointing up.
+/// APIs that take a corner also accept an `i32`, wrapped (not clamped) to the range [0, 5].
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HexCorner {
+    Zero,
+    One,
+    Two,
+    Three,
+    Four,
+    Five,
+}
+
+impl HexCorner {
+    // constants
+    pub const ALL: [HexCorner; 6] = [
+        HexCorner::Zero,
+        HexCorner::One,
+        HexCorner::Two,
+        HexCorner::Three,
+        HexCorner::Four,
+        HexCorner::Five,
+    ];
+
+    pub const FLAT_EAST: HexCorner = HexCorner::Zero;
+    pub const FLAT_NORTH_EAST: HexCorner = HexCorner::One;
+    pub const FLAT_NORTH_WEST: HexCorner = HexCorner::Two;
+    pub const FLAT_WEST: HexCorner = HexCorner::Three;
+    pub const FLAT_SOUTH_WEST: HexCorner = HexCorner::Four;
+    pub const FLAT_SOUTH_EAST: HexCorner = HexCorner::Five;
+
+    pub const POINTY_NORTH_EAST: HexCorner = HexCorner::Zero;
+    pub const POINTY_NORTH: HexCorner = HexCorner::One;
+    pub const POINTY_NORTH_WEST: HexCorner = HexCorner::Two;
+    pub const POINTY_SOUTH_WEST: HexCorner = HexCorner::Three;
+    pub const POINTY_SOUTH: HexCorner = HexCorner::Four;
+    pub const POINTY_SOUTH_EAST: HexCorner = HexCorner::Five;
+
+    // methods
+    /// Returns the offset from a hex's center to this corner.
+    pub fn unit_vertex(&self) -> HexVertex {
+        HexVertex::get_unit_coord(*self)
+    }
+
+    /// Returns the two directions whose neighbors share this corner, in CCW order.
+    pub fn directions(&self) -> (HexDirection, HexDirection) {
+        (HexDirection::new(self.index()), HexDirection::new(self.index() + 1))
+    }
+}
+
+// index conversion, rotation, and iteration shared by `HexDirection` and `HexCorner`
+macro_rules! impl_hex_index {
+    ($t:ident) => {
+        impl $t {
+            /// Returns the variant with index `i`, wrapped (not clamped) to the range [0, 5].
+            pub fn new(i: i32) -> $t {
+                $t::ALL[i.rem_euclid(6) as usize]
+            }
+
+            /// Returns the index of `self`, in the range [0, 5].
+            pub fn index(&self) -> i32 {
+                *self as i32
+            }
+
+            /// Returns `self` rotated `rotation` sixth-turns CCW.
+            pub fn rotated(&self, rotation: i32) -> $t {
+                $t::new(self.index() + rotation)
+            }
+
+            /// Returns the variant a half-turn from `self`.
+            pub fn opposite(&self) -> $t {
+                self.rotated(3)
+            }
+
+            /// Returns an iterator over all six variants, starting from `Zero`.
+            pub fn iter() -> impl Iterator<Item = $t> {
+                $t::ALL.into_iter()
+            }
+        }
+
+        impl From<i32> for $t {
+            fn from(item: i32) -> $t {
+                $t::new(item)
+            }
+        }
+
+        impl From<$t> for i32 {
+            fn from(item: $t) -> i32 {
+                item.index()
+            }
+        }
+
+        impl ops::Add<i32> for $t {
+            type Output = $t;
+
+            fn add(self, other: i32) -> $t {
+                self.rotated(other)
+            }
+        }
+
+        impl ops::AddAssign<i32> for $t {
+            fn add_assign(&mut self, other: i32) {
+                *self = *self + other;
+            }
+        }
+
+        impl ops::Sub<i32> for $t {
+            type Output = $t;
+
+            fn sub(self, other: i32) -> $t {
+                self.rotated(-other)
+            }
+        }
+
+        impl ops::SubAssign<i32> for $t {
+            fn sub_assign(&mut self, other: i32) {
+                *self = *self - other;
+            }
+        }
+
+        /// Returns the number of sixth-turns CCW, in the range [0, 5], that rotates `other` to `self`.
+        impl ops::Sub for $t {
+            type Output = i32;
+
+            fn sub(self, other: $t) -> i32 {
+                (self.index() - other.index()).rem_euclid(6)
+            }
+        }
+
+        impl ops::Neg for $t {
+            type Output = $t;
+
+            fn neg(self) -> $t {
+                self.opposite()
+            }
+        }
+
+        impl ops::Mul<$t> for HexTransform {
+            type Output = $t;
+
+            fn mul(self, other: $t) -> $t {
+                other.rotated(self.rotation())
+            }
+        }
+    };
+}
+
+impl_hex_index!(HexDirection);
+impl_hex_index!(HexCorner);
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn directions_match_indices() {
+        let hex = HexCoord::new(2, -3);
+        for (i, direction) in HexDirection::iter().enumerate() {
+            let i = i as i32;
+            assert_eq!(direction.index(), i);
+            assert_eq!(hex.get_neighbor(direction), hex.get_neighbor(i));
+            assert_eq!(hex.get_neighbor(direction.opposite()), hex - direction.unit_coord());
+            assert_eq!(hex.direction_to(hex.get_neighbor(direction)), Some(direction));
+
+            let (a, b) = direction.corners();
+            assert_eq!(hex.get_half_edge(a).twin().hex(), hex.get_neighbor(direction));
+            assert_eq!(hex.get_vertex(b), hex.get_half_edge(a).destination());
+        }
+        assert_eq!(hex.direction_to(hex), None);
+        assert_eq!(hex.direction_to(hex + HexCoord::new(2, -2)), None);
+    }
+
+    #[test]
+    fn rotation_arithmetic() {
+        assert_eq!(HexDirection::Five + 2, HexDirection::One);
+        assert_eq!(HexDirection::One - 3, HexDirection::Four);
+        assert_eq!(HexDirection::new(-1), HexDirection::Five);
+        assert_eq!(HexDirection::Two - HexDirection::Four, 4);
+        assert_eq!(-HexCorner::FLAT_EAST, HexCorner::FLAT_WEST);
+        assert_eq!(HexCorner::POINTY_NORTH.opposite(), HexCorner::POINTY_SOUTH);
+
+        let transform = HexTransform::new(HexCoord::new(1, 4), HexDirection::Two);
+        assert_eq!(transform, HexTransform::new(HexCoord::new(1, 4), 8));
+        assert_eq!(transform.rotation_direction() - transform.inverse().rotation_direction(), 4);
+        assert_eq!(transform.rotation(), 2);
+        assert_eq!(HexCoord::new(2, 0).rotate_around(HexCoord::ZERO, HexDirection::One), HexCoord::new(0, 2));
+        let hex = HexCoord::new(-3, 1);
+        for direction in HexDirection::iter() {
+            assert_eq!(transform * hex.get_neighbor(direction), (transform * hex).get_neighbor(transform * direction));
+        }
+        for corner in HexCorner::iter() {
+            assert_eq!(corner.unit_vertex().translate(&hex), hex.get_vertex(corner));
+            assert_eq!(hex.get_vertex(corner).get_outgoing_edge_toward(corner), hex.get_diagonal_edge(corner));
+            assert_eq!(hex.get_vertex(corner).get_outgoing_edge_toward(corner.opposite()), hex.get_half_edge(corner));
+            assert_eq!(hex.get_vertex(corner).get_incoming_edge_from(corner + 2), hex.get_half_edge(corner).twin());
+            let (a, b) = corner.directions();
+            assert_eq!(hex.get_neighbor(a).direction_to(hex.get_neighbor(b)), Some(a + 2));
+        }
+    }
+
+    #[cfg(feature = "cgmath")]
+    #[test]
+    fn layout_names_match_positions() {
+        use cgmath::Vector3;
+
+        let up = Vector3::new(0.0, 0.0, 1.0);
+        let flat: HexField<f32, Vector3<f32>> = HexField::with_layout(Vector3::new(0.0, 0.0, 0.0), up, Vector3::new(0.0, 1.0, 0.0), HexOrientation::FlatTop, Handedness::RightHanded, 1.0);
+        let pointy: HexField<f32, Vector3<f32>> = HexField::with_layout(Vector3::new(0.0, 0.0, 0.0), up, Vector3::new(0.0, 1.0, 0.0), HexOrientation::PointyTop, Handedness::RightHanded, 1.0);
+
+        assert!(flat.get_position(HexCoord::ZERO.get_neighbor(HexDirection::FLAT_NORTH)).y > 1.5);
+        assert!(flat.get_position(HexCoord::ZERO.get_vertex(HexCorner::FLAT_EAST)).x > 0.99);
+        assert!(pointy.get_position(HexCoord::ZERO.get_neighbor(HexDirection::POINTY_EAST)).x > 1.5);
+        assert!(pointy.get_position(HexCoord::ZERO.get_vertex(HexCorner::POINTY_NORTH)).y > 0.99);
+
+        let south_east = flat.get_position(HexCoord::ZERO.get_neighbor(HexDirection::FLAT_SOUTH_EAST));
+        assert!(south_east.x > 0.0 && south_east.y < 0.0);
+    }
+}
@@ -1,12 +1,29 @@
 use crate::*;
-use cgmath::Vector3;
-use cgmath::InnerSpace;
+use crate::hex_vector::{HexVector3, DefaultVector3, Vector3};
+use std::marker::PhantomData;
 
+/// Which way hexes face relative to a `HexField`'s y-axis.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum HexOrientation {
+    /// Hexes have an edge at the top; hex *(0, 1, -1)* lies along the positive y-axis.
     FlatTop,
+    /// Hexes have a vertex at the top; hex *(0, 1, -1)* lies 30 degrees CCW of the positive y-axis.
     PointyTop,
 }
 
+impl HexOrientation {
+    // angle from the x-axis to the q basis vector
+    pub(crate) fn basis_angle(&self) -> f64 {
+        match self {
+            HexOrientation::FlatTop => 0.0,
+            HexOrientation::PointyTop => std::f64::consts::TAU / 12.0,
+        }
+    }
+}
+
+/// Whether a `HexField`'s x, y, and normal axes form a right-handed or left-handed frame.
+/// Looking down at the grid from the normal, hex vertices and neighbors are numbered CCW in a right-handed field and CW in a left-handed one.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum Handedness {
     LeftHanded,
     RightHanded,
@@ -20,38 +37,67 @@ pub enum Handedness {
 /// Likewise, you can create a `HexField` rotated and positioned to be on an overhead display rectangle in worldspace.
 /// 
 /// When querying which hex contains a worldspace point, `HexField` projects the query point onto the plane of the hex grid.
-pub struct HexField {
-    origin: Vector3<f32>,
+///
+/// `HexField` accepts and returns vectors of type `V`, which can be cgmath, glam, or mint vectors depending on the enabled cargo features, or plain arrays.
+pub struct HexField<F: HexFloat = f32, V: HexVector3<F> = DefaultVector3<F>> {
+    origin: Vector3<F>,
     
-    x_basis: Vector3<f32>,
-    y_basis: Vector3<f32>,
-    z_basis: Vector3<f32>,
+    x_basis: Vector3<F>,
+    y_basis: Vector3<F>,
+    z_basis: Vector3<F>,
+
+    q_basis: Vector3<F>,
+    r_basis: Vector3<F>,
+    s_basis: Vector3<F>,
+
+    _inner_radius: F,
+    outer_radius: F,
 
-    q_basis: Vector3<f32>,
-    r_basis: Vector3<f32>,
-    s_basis: Vector3<f32>,
+    orientation: HexOrientation,
+    handedness: Handedness,
 
-    _inner_radius: f32,
-    outer_radius: f32,
+    vector: PhantomData<V>,
 }
 
-impl HexField {
+impl<F: HexFloat, V: HexVector3<F>> HexField<F, V> {
+    /// Returns the worldspace position of the center of hex *(0, 0, 0)*.
+    pub fn origin(&self) -> V {
+        self.origin.to_hex_vector()
+    }
+
     /// You can conceptualize a `HexField` as a plane with a hex grid on it, positioned and oriented somewhere in worldspace.
     /// This function returns the worldspace direction of this plane's x-axis.
-    pub fn x_basis(&self) -> Vector3<f32> {
-        self.x_basis
+    pub fn x_basis(&self) -> V {
+        self.x_basis.to_hex_vector()
     }
 
     /// You can conceptualize a `HexField` as a plane with a hex grid on it, positioned and oriented somewhere in worldspace.
     /// This function returns the worldspace direction of this plane's x-axis.
-    pub fn y_basis(&self) -> Vector3<f32> {
-        self.y_basis
+    pub fn y_basis(&self) -> V {
+        self.y_basis.to_hex_vector()
     }
 
     /// You can conceptualize a `HexField` as a plane with a hex grid on it, positioned and oriented somewhere in worldspace.
     /// This function returns the worldspace direction normal to this plane's surface.
-    pub fn z_basis(&self) -> Vector3<f32> {
-        self.z_basis
+    pub fn z_basis(&self) -> V {
+        self.z_basis.to_hex_vector()
+    }
+
+    /// Returns whether hexes in `self` are flat-top or pointy-top relative to its y-axis.
+    /// Fields created with `new` are always flat-top, because `pos_y_hex_displacement` defines their y-axis.
+    pub fn orientation(&self) -> HexOrientation {
+        self.orientation
+    }
+
+    /// Returns whether the x, y, and normal axes of `self` form a right-handed or left-handed frame.
+    /// Fields created with `new` are always right-handed.
+    pub fn handedness(&self) -> Handedness {
+        self.handedness
+    }
+
+    /// Returns the distance from the center of each hex to its vertices.
+    pub fn size(&self) -> F {
+        self.outer_radius
     }
 
     /// Creates a `HexField` with the specified parameters.
@@ -59,31 +105,73 @@ impl HexField {
     /// * `z_direction`: The worldspace direction of `up` on the hex grid, perpendicular to the plane of hexes
     /// * `pos_y_hex_displacement`: The vector from `origin` to the center of hex *(0, 1, -1)*.  Hex *(0, 1, -1)* is adjacent to `origin`; you can control the scale and rotation (around `z_direction`) of the `HexField` via this parameter.
     pub fn new(
-        origin: Vector3<f32>,
-        z_direction: Vector3<f32>,
-        pos_y_hex_displacement: Vector3<f32>
-    ) -> HexField {
-        let z_relative = z_direction - origin;
+        origin: V,
+        z_direction: V,
+        pos_y_hex_displacement: V
+    ) -> HexField<F, V> {
+        let origin = Vector3::from_hex_vector(origin);
+        let pos_y_hex_displacement = Vector3::from_hex_vector(pos_y_hex_displacement);
+
+        let z_relative = Vector3::from_hex_vector(z_direction) - origin;
         let y_basis = pos_y_hex_displacement.normalize();
 
         // orthonormalize z against y and calculate x
-        let z_basis = (z_relative - InnerSpace::dot(z_relative, y_basis) * z_relative).normalize();
-        let x_basis = Vector3::cross(y_basis, z_basis);
-
-        // calculate q, r, and s (degenerate) basis
-        let q_basis = x_basis;
-        let r_basis =
-            f32::cos(std::f32::consts::TAU / 3.0) * x_basis +
-            f32::sin(std::f32::consts::TAU / 3.0) * y_basis;
-        let s_basis =
-            f32::cos(2.0 * std::f32::consts::TAU / 3.0) * x_basis +
-            f32::sin(2.0 * std::f32::consts::TAU / 3.0) * y_basis;
-        
-        // calculate inner and outer radius
-        let inner_radius = pos_y_hex_displacement.magnitude() / 2.0;
-        let outer_radius = inner_radius * 2.0 / f32::sqrt(3.0);
+        let z_basis = (z_relative - z_relative * z_relative.dot(y_basis)).normalize();
+        let x_basis = y_basis.cross(z_basis);
+
+        // calculate outer radius
+        let inner_radius = pos_y_hex_displacement.magnitude() / F::from_f64(2.0);
+        let outer_radius = inner_radius * F::from_f64(2.0) / F::from_f64(3.0).sqrt();
+
+        HexField::from_basis(origin, x_basis, y_basis, z_basis, outer_radius, HexOrientation::FlatTop, Handedness::RightHanded)
+    }
+
+    /// Creates a `HexField` with an explicit layout.
+    /// * `origin`: The worldspace position of the center of hex *(0, 0, 0)*.
+    /// * `normal`: The worldspace direction of `up` on the hex grid, perpendicular to the plane of hexes.
+    /// * `y_direction`: The worldspace direction of the field's y-axis, the "top" used by `orientation`.  Only its component perpendicular to `normal` is used.
+    /// * `orientation`: Whether hexes have an edge or a vertex at the top.
+    /// * `handedness`: Whether the x-axis is `y_direction` cross `normal` (right-handed) or its opposite (left-handed).
+    /// * `size`: The distance from the center of each hex to its vertices.
+    pub fn with_layout(
+        origin: V,
+        normal: V,
+        y_direction: V,
+        orientation: HexOrientation,
+        handedness: Handedness,
+        size: F,
+    ) -> HexField<F, V> {
+        let y_direction = Vector3::from_hex_vector(y_direction);
+
+        let z_basis = Vector3::from_hex_vector(normal).normalize();
+        let y_basis = (y_direction - z_basis * y_direction.dot(z_basis)).normalize();
+        let x_basis = match handedness {
+            Handedness::RightHanded => y_basis.cross(z_basis),
+            Handedness::LeftHanded => z_basis.cross(y_basis),
+        };
+
+        HexField::from_basis(Vector3::from_hex_vector(origin), x_basis, y_basis, z_basis, size, orientation, handedness)
+    }
+
+    fn from_basis(
+        origin: Vector3<F>,
+        x_basis: Vector3<F>,
+        y_basis: Vector3<F>,
+        z_basis: Vector3<F>,
+        outer_radius: F,
+        orientation: HexOrientation,
+        handedness: Handedness,
+    ) -> HexField<F, V> {
+        // calculate q, r, and s (degenerate) basis, a third of a turn apart starting from the orientation's angle
+        let angle = orientation.basis_angle();
+        let basis = |turns: f64| {
+            let angle = F::from_f64(angle + turns * std::f64::consts::TAU);
+            x_basis * angle.cos() + y_basis * angle.sin()
+        };
+        let q_basis = basis(0.0);
+        let r_basis = basis(1.0 / 3.0);
+        let s_basis = basis(2.0 / 3.0);
 
-        // return struct
         HexField {
             origin,
 
@@ -95,12 +183,42 @@ impl HexField {
             r_basis,
             s_basis,
 
-            _inner_radius: inner_radius,
+            _inner_radius: outer_radius * F::from_f64(3.0).sqrt() / F::from_f64(2.0),
             outer_radius,
+
+            orientation,
+            handedness,
+
+            vector: PhantomData,
+        }
+    }
+
+
+    /// Converts `self` to use the scalar type `G`, rounding to the nearest representable value.
+    /// The vector type changes to `W`.
+    pub fn cast<G: HexFloat, W: HexVector3<G>>(&self) -> HexField<G, W> {
+        HexField {
+            origin: self.origin.cast(),
+
+            x_basis: self.x_basis.cast(),
+            y_basis: self.y_basis.cast(),
+            z_basis: self.z_basis.cast(),
+
+            q_basis: self.q_basis.cast(),
+            r_basis: self.r_basis.cast(),
+            s_basis: self.s_basis.cast(),
+
+            _inner_radius: G::from_f64(self._inner_radius.as_f64()),
+            outer_radius: G::from_f64(self.outer_radius.as_f64()),
+
+            orientation: self.orientation,
+            handedness: self.handedness,
+
+            vector: PhantomData,
         }
     }
 
-    fn project_onto_basis(point: Vector3<f32>, basis_0: Vector3<f32>, basis_1: Vector3<f32>, basis_2: Vector3<f32>) -> Vector3<f32> {
+    fn project_onto_basis(point: Vector3<F>, basis_0: Vector3<F>, basis_1: Vector3<F>, basis_2: Vector3<F>) -> Vector3<F> {
         let denominator =
             basis_0.x * basis_1.y * basis_2.z - basis_0.z * basis_1.y * basis_2.x +
             basis_0.y * basis_1.z * basis_2.x - basis_0.x * basis_1.z * basis_2.y +
@@ -129,22 +247,28 @@ impl HexField {
     }
 
     /// Projects an arbitrary vector to lie on the plane of the hex grid.  Returns a result in the local cartesian coordinates of the hex grid.
-    pub fn project_onto_plane(&self, position: Vector3<f32>) -> Vector3<f32> {
-        HexField::project_onto_basis(position - self.origin, self.x_basis, self.y_basis, self.z_basis)
+    pub fn project_onto_plane(&self, position: V) -> V {
+        Self::project_onto_basis(Vector3::from_hex_vector(position) - self.origin, self.x_basis, self.y_basis, self.z_basis).to_hex_vector()
     }
 
     /// Returns the exact, fractional coordinates of a given worldspace vector projected onto the hex grid.
-    pub fn get_hex_coord_fraction(&self, position: Vector3<f32>) -> HexCoordFraction {
+    pub fn get_hex_coord_fraction(&self, position: V) -> HexCoordFraction<F> {
+        self.hex_coord_fraction(Vector3::from_hex_vector(position))
+    }
+
+    pub(crate) fn hex_coord_fraction(&self, position: Vector3<F>) -> HexCoordFraction<F> {
         // calculate q and r coordinates that do not respect the q + r + s = 0 invariant
-        let qr = HexField::project_onto_basis((position - self.origin) / self.outer_radius, self.q_basis, self.r_basis, self.z_basis);
+        let qr = Self::project_onto_basis((position - self.origin) / self.outer_radius, self.q_basis, self.r_basis, self.z_basis);
 
         // recalculate qrs to keep the same position but also respect the q + r + s = 0 invariant
         let q = qr.x;
         let r = qr.y; // and s starts as 0, breaking the invariant
-        let a: f32 = -0.5; // horizontal component of q and r vectors
+        let a = F::from_f64(-0.5); // horizontal component of q and r vectors
+        let one = F::one();
+        let two = F::from_f64(2.0);
 
-        let qp = (q + a * r) / (1.0 - a); // hold position constant and solve for qp, rp to satisfy invariant
-        let rp = (q + r * (2.0 * a - 1.0)) / (2.0 * a - 2.0);
+        let qp = (q + a * r) / (one - a); // hold position constant and solve for qp, rp to satisfy invariant
+        let rp = (q + r * (two * a - one)) / (two * a - two);
 
         HexCoordFraction::new(
             qp,
@@ -153,40 +277,249 @@ impl HexField {
     }
 
     /// Returns the hex coordinates, rounded to the nearest hex, of a given worldspace vector projected onto the hex grid.
-    pub fn get_hex_coord(&self, position: Vector3<f32>) -> HexCoord {
+    pub fn get_hex_coord(&self, position: V) -> HexCoord {
         self.get_hex_coord_fraction(position).round()
     }
 
     /// Returns the worldspace coordinates of a hex coordinate.
-    pub fn get_position<T: Into<HexCoordFraction>>(&self, coord: T) -> Vector3<f32> {
-        let coord = coord.into();
-        self.outer_radius * (coord.q() * self.q_basis + coord.r() * self.r_basis + coord.s() * self.s_basis)
+    pub fn get_position<T: Into<HexCoordFraction<F>>>(&self, coord: T) -> V {
+        self.position(coord.into()).to_hex_vector()
+    }
+
+    pub(crate) fn position(&self, coord: HexCoordFraction<F>) -> Vector3<F> {
+        self.origin + (self.q_basis * coord.q() + self.r_basis * coord.r() + self.s_basis * coord.s()) * self.outer_radius
     }
 
     /// Returns the worldspace coordinates of a hex coordinate, translated to be at `height` above the plane of the hex grid.
-    pub fn get_position_with_height<T: Into<HexCoordFraction>>(&self, coord: T, height: f32) -> Vector3<f32> {
-        self.get_position(coord) + height * self.z_basis
+    pub fn get_position_with_height<T: Into<HexCoordFraction<F>>>(&self, coord: T, height: F) -> V {
+        self.position_with_height(coord.into(), height).to_hex_vector()
+    }
+
+    pub(crate) fn position_with_height(&self, coord: HexCoordFraction<F>, height: F) -> Vector3<F> {
+        self.position(coord) + self.z_basis * height
     }
 
     /// Returns the worldspace coordinates of the `i`th vertex of the hex at the given coordinates, translated to be at `height` above the plane of the hex grid.
-    pub fn get_face_vertex_position(&self, face: HexCoord, scale: f32, height: f32, i: i32) -> Vector3<f32> {
-        let center = self.get_position_with_height(face, height);
-        let outer = self.get_position_with_height(face.get_vertex(i), height);
-        center + scale * (outer - center)
+    pub fn get_face_vertex_position<C: Into<HexCorner>>(&self, face: HexCoord, scale: F, height: F, i: C) -> V {
+        self.scaled_vertex_position(face, face.get_vertex(i), scale, height).to_hex_vector()
+    }
+
+    pub fn get_source_vertex_position(&self, edge: HexHalfEdge, scale: F, height: F) -> V {
+        self.scaled_vertex_position(edge.hex(), edge.source(), scale, height).to_hex_vector()
+    }
+
+    pub fn get_destination_vertex_position(&self, edge: HexHalfEdge, scale: F, height: F) -> V {
+        self.scaled_vertex_position(edge.hex(), edge.destination(), scale, height).to_hex_vector()
+    }
+
+    /// Intersects a ray with the plane of the hex grid, as for mouse picking.
+    /// Returns the hex containing the intersection along with the worldspace intersection point,
+    /// or `None` if the ray is parallel to the plane or points away from it.
+    pub fn raycast(&self, origin: V, direction: V) -> Option<(HexCoord, V)> {
+        let hit = self.ray_plane_intersection(Vector3::from_hex_vector(origin), Vector3::from_hex_vector(direction))?;
+        Some((self.hex_coord_fraction(hit).round(), hit.to_hex_vector()))
     }
 
-    pub fn get_source_vertex_position(&self, edge: HexHalfEdge, scale: f32, height: f32) -> Vector3<f32> {
-        let center = self.get_position_with_height(edge.hex(), height);
-        let outer = self.get_position_with_height(edge.source(), height);
+    /// Like `raycast`, but returns the vertex nearest to the intersection point.
+    pub fn raycast_vertex(&self, origin: V, direction: V) -> Option<(HexVertex, V)> {
+        let hit = self.ray_plane_intersection(Vector3::from_hex_vector(origin), Vector3::from_hex_vector(direction))?;
+        Some((self.hex_coord_fraction(hit).round_to_vertex().0, hit.to_hex_vector()))
+    }
+
+    /// Like `raycast`, but returns the edge nearest to the intersection point, as the half-edge belonging to the hit hex.
+    pub fn raycast_half_edge(&self, origin: V, direction: V) -> Option<(HexHalfEdge, V)> {
+        let hit = self.ray_plane_intersection(Vector3::from_hex_vector(origin), Vector3::from_hex_vector(direction))?;
+        Some((self.hex_coord_fraction(hit).round_to_edge().0, hit.to_hex_vector()))
+    }
+
+    /// Returns the vertex nearest to a worldspace position projected onto the hex grid, along with the worldspace distance to it within the plane.
+    pub fn get_nearest_vertex(&self, position: V) -> (HexVertex, F) {
+        let (vertex, distance) = self.get_hex_coord_fraction(position).round_to_vertex();
+        (vertex, distance * self.outer_radius)
+    }
+
+    /// Returns the edge nearest to a worldspace position projected onto the hex grid, along with the worldspace distance to it within the plane.
+    /// The edge is returned as the half-edge belonging to the hex containing the position.
+    pub fn get_nearest_edge(&self, position: V) -> (HexHalfEdge, F) {
+        let (edge, distance) = self.get_hex_coord_fraction(position).round_to_edge();
+        (edge, distance * self.outer_radius)
+    }
+
+    fn ray_plane_intersection(&self, origin: Vector3<F>, direction: Vector3<F>) -> Option<Vector3<F>> {
+        let denominator = direction.dot(self.z_basis);
+        if denominator.abs() <= F::epsilon() * direction.magnitude() {
+            return None;
+        }
+
+        let t = (self.origin - origin).dot(self.z_basis) / denominator;
+        if t < F::zero() {
+            return None;
+        }
+
+        Some(origin + direction * t)
+    }
+
+    fn scaled_vertex_position(&self, face: HexCoord, vertex: HexVertex, scale: F, height: F) -> Vector3<F> {
+        let center = self.position_with_height(face.into(), height);
+        let outer = self.position_with_height(vertex.into(), height);
+
+        center + (outer - center) * scale
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn layouts() -> Vec<HexField<f32, [f32; 3]>> {
+        let mut fields = Vec::new();
+        for orientation in [HexOrientation::FlatTop, HexOrientation::PointyTop] {
+            for handedness in [Handedness::RightHanded, Handedness::LeftHanded] {
+                fields.push(HexField::with_layout(
+                    [1.0, 2.0, 3.0],
+                    [0.0, 0.0, 1.0],
+                    [0.0, 1.0, 0.0],
+                    orientation,
+                    handedness,
+                    2.0,
+                ));
+            }
+        }
+        fields
+    }
+
+    #[test]
+    fn layout_round_trips() {
+        for field in layouts() {
+            for hex in HexRangeIterator::new(3, HexTransform::IDENTITY) {
+                assert_eq!(field.get_hex_coord(field.get_position(hex)), hex);
+            }
+        }
+    }
+
+    #[test]
+    fn layout_accessors_and_geometry() {
+        for field in layouts() {
+            // positions relative to the origin of the field
+            let relative = |hex: HexCoord| {
+                let ([x, y, z], [ox, oy, oz]) = (field.get_position(hex), field.origin());
+                [x - ox, y - oy, z - oz]
+            };
+
+            let [x, y, _] = relative(HexCoord::new(0, 1));
+            let expected_angle = match field.orientation() {
+                HexOrientation::FlatTop => 0.0,
+                HexOrientation::PointyTop => std::f32::consts::TAU / 12.0,
+            };
+            let length = f32::hypot(x, y);
+            assert!((y / length - f32::cos(expected_angle)).abs() < 1e-5);
+            assert!((length - 2.0 * f32::sqrt(3.0)).abs() < 1e-5);
+
+            // neighbor 1 comes after neighbor 0 going CCW when viewed from the normal exactly when right-handed
+            let [x0, y0, _] = relative(HexCoord::ZERO.get_neighbor(0));
+            let [x1, y1, _] = relative(HexCoord::ZERO.get_neighbor(1));
+            assert_eq!(x0 * y1 - y0 * x1 > 0.0, field.handedness() == Handedness::RightHanded);
+        }
+
+        let default: HexField<f32, [f32; 3]> = HexField::new([0.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]);
+        assert_eq!(default.orientation(), HexOrientation::FlatTop);
+        assert_eq!(default.handedness(), Handedness::RightHanded);
+    }
+
+    #[test]
+    fn f64_field_is_precise_far_from_origin() {
+        let field: HexField<f64, [f64; 3]> = HexField::new([0.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]);
+        let hex = HexCoord::new(4_000_000, -3_000_000);
+        for i in 0..6 {
+            let near_corner = field.get_face_vertex_position(hex, 0.999, 0.0, i);
+            assert_eq!(field.get_hex_coord(near_corner), hex);
+        }
 
-        center + scale * (outer - center)
+        let cast: HexField<f32, [f32; 3]> = field.cast();
+        assert_eq!(cast.get_hex_coord(cast.get_position(HexCoord::new(3, -1))), HexCoord::new(3, -1));
     }
 
-    pub fn get_destination_vertex_position(&self, edge: HexHalfEdge, scale: f32, height: f32) -> Vector3<f32> {
-        let center = self.get_position_with_height(edge.hex(), height);
-        let outer = self.get_position_with_height(edge.destination(), height);
+    #[test]
+    fn raycast_hits_plane() {
+        let field: HexField<f32, [f32; 3]> = HexField::new([0.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]);
+        let [x, y, _] = field.get_position(HexCoord::new(2, -3));
+
+        let (hex, hit) = field.raycast([x, y, 10.0], [0.0, 0.0, -1.0]).unwrap();
+        assert_eq!(hex, HexCoord::new(2, -3));
+        assert!(hit[2].abs() < 1e-5);
+
+        // slanted ray toward the same point
+        let (hex, _) = field.raycast([x - 5.0, y + 5.0, 5.0], [5.0, -5.0, -5.0]).unwrap();
+        assert_eq!(hex, HexCoord::new(2, -3));
 
-        center + scale * (outer - center)
+        assert!(field.raycast([x, y, 10.0], [0.0, 0.0, 1.0]).is_none());
+        assert!(field.raycast([x, y, 10.0], [1.0, 0.0, 0.0]).is_none());
+    }
+
+    #[test]
+    fn raycast_vertex_and_edge() {
+        let field: HexField<f32, [f32; 3]> = HexField::new([0.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]);
+        let hex = HexCoord::new(-1, 2);
+        for i in 0..6 {
+            let [x, y, _] = field.get_face_vertex_position(hex, 0.9, 0.0, i);
+            let (vertex, _) = field.raycast_vertex([x, y, 1.0], [0.0, 0.0, -1.0]).unwrap();
+            assert_eq!(vertex, hex.get_vertex(i));
+
+            let [x0, y0, _] = field.get_face_vertex_position(hex, 0.9, 0.0, i);
+            let [x1, y1, _] = field.get_face_vertex_position(hex, 0.9, 0.0, i + 1);
+            let (edge, _) = field.raycast_half_edge([(x0 + x1) / 2.0, (y0 + y1) / 2.0, 1.0], [0.0, 0.0, -1.0]).unwrap();
+            assert_eq!(edge, hex.get_half_edge(i));
+        }
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn nearest_vertex_and_edge_distances() {
+        let field: HexField<f32, [f32; 3]> = HexField::new([0.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 2.0, 0.0]);
+        let hex = HexCoord::new(1, 1);
+
+        let [x, y, _] = field.get_face_vertex_position(hex, 0.5, 0.0, 2);
+        let (vertex, distance) = field.get_nearest_vertex([x, y, 7.0]);
+        assert_eq!(vertex, hex.get_vertex(2));
+        assert!((distance - field.size() * 0.5).abs() < 1e-5);
+
+        let [x, y, _] = field.get_position(hex);
+        let (_, distance) = field.get_nearest_edge([x, y, 0.0]);
+        assert!((distance - 1.0).abs() < 1e-5);
+    }
+
+    #[cfg(feature = "cgmath")]
+    #[test]
+    fn bare_types_use_cgmath_vectors() {
+        use cgmath::{Vector2, Vector3};
+        let field: HexField = HexField::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 1.0), Vector3::new(0.0, 1.0, 0.0));
+        let position: Vector3<f32> = field.get_position(HexCoord::new(2, -1));
+        assert_eq!(field.get_hex_coord(position), HexCoord::new(2, -1));
+
+        let field2: HexField2 = HexField2::new(Vector2::new(0.0, 0.0), Vector2::new(1.0, 1.0), HexOrientation::FlatTop, Handedness::RightHanded);
+        let position: Vector2<f32> = field2.get_position(HexCoord::new(2, -1));
+        assert_eq!(field2.get_hex_coord(position), HexCoord::new(2, -1));
+    }
+
+    #[cfg(feature = "glam")]
+    #[test]
+    fn glam_vectors() {
+        let field = HexField::new(glam::Vec3::ZERO, glam::Vec3::Z, glam::Vec3::Y);
+        let position: glam::Vec3 = field.get_position(HexCoord::new(2, -1));
+        assert_eq!(field.get_hex_coord(position), HexCoord::new(2, -1));
+
+        let precise = HexField::new(glam::DVec3::ZERO, glam::DVec3::Z, glam::DVec3::Y);
+        assert_eq!(precise.get_hex_coord(precise.get_position(HexCoord::new(2, -1))), HexCoord::new(2, -1));
+    }
+
+    #[cfg(feature = "mint")]
+    #[test]
+    fn mint_vectors() {
+        let field = HexField::new(
+            mint::Vector3 { x: 0.0f32, y: 0.0, z: 0.0 },
+            mint::Vector3 { x: 0.0, y: 0.0, z: 1.0 },
+            mint::Vector3 { x: 0.0, y: 1.0, z: 0.0 },
+        );
+        assert_eq!(field.get_hex_coord(field.get_position(HexCoord::new(-3, 1))), HexCoord::new(-3, 1));
+    }
+}
@@ -0,0 +1,179 @@
+use crate::*;
+use crate::hex_vector::{HexVector2, DefaultVector2, Vector2};
+use std::marker::PhantomData;
+
+/// A mapping between a 2D coordinate system, such as screen pixels, and hex coordinates.
+/// Contains methods to determine which hex contains a point and calculate the positions of hex cells and vertices.
+///
+/// Unlike `HexField`, `HexField2` supports non-uniform scaling, so hexes can be stretched to fit pixel art or an editor grid.
+/// Use `Handedness::LeftHanded` for y-down screen coordinates: positions are mirrored vertically so the grid appears on screen
+/// as a right-handed field does in a y-up plot, with hex *(0, 1, -1)* above the origin for flat-top fields.
+///
+/// `HexField2` accepts and returns vectors of type `V`, which can be cgmath, glam, or mint vectors depending on the enabled cargo features, or plain arrays.
+pub struct HexField2<F: HexFloat = f32, V: HexVector2<F> = DefaultVector2<F>> {
+    origin: Vector2<F>,
+    size: Vector2<F>,
+
+    q_basis: Vector2<F>,
+    r_basis: Vector2<F>,
+    s_basis: Vector2<F>,
+
+    orientation: HexOrientation,
+    handedness: Handedness,
+
+    vector: PhantomData<V>,
+}
+
+impl<F: HexFloat, V: HexVector2<F>> HexField2<F, V> {
+    // accessors
+    /// Returns the position of the center of hex *(0, 0, 0)*.
+    pub fn origin(&self) -> V {
+        self.origin.to_hex_vector()
+    }
+
+    /// Returns the distance from the center of each hex to its vertices, along the x-axis and y-axis respectively.
+    pub fn size(&self) -> V {
+        self.size.to_hex_vector()
+    }
+
+    /// Returns whether hexes in `self` are flat-top or pointy-top.
+    pub fn orientation(&self) -> HexOrientation {
+        self.orientation
+    }
+
+    /// Returns whether `self` uses y-up (right-handed) or y-down (left-handed) coordinates.
+    pub fn handedness(&self) -> Handedness {
+        self.handedness
+    }
+
+    // constructor
+    /// Creates a `HexField2` with the specified parameters.
+    /// * `origin`: The position of the center of hex *(0, 0, 0)*.
+    /// * `size`: The distance from the center of each hex to its vertices, along the x-axis and y-axis respectively.  Unequal components stretch the hexes.
+    /// * `orientation`: Whether hexes have an edge or a vertex at the top.
+    /// * `handedness`: `RightHanded` for y-up coordinates, `LeftHanded` for y-down screen coordinates.
+    pub fn new(
+        origin: V,
+        size: V,
+        orientation: HexOrientation,
+        handedness: Handedness,
+    ) -> HexField2<F, V> {
+        let size = Vector2::from_hex_vector(size);
+        let y_sign = match handedness {
+            Handedness::RightHanded => F::one(),
+            Handedness::LeftHanded => -F::one(),
+        };
+
+        // q, r, and s (degenerate) basis, a third of a turn apart starting from the orientation's angle
+        let angle = orientation.basis_angle();
+        let basis = |turns: f64| {
+            let angle = F::from_f64(angle + turns * std::f64::consts::TAU);
+            Vector2::new(size.x * angle.cos(), y_sign * size.y * angle.sin())
+        };
+
+        HexField2 {
+            origin: Vector2::from_hex_vector(origin),
+            size,
+
+            q_basis: basis(0.0),
+            r_basis: basis(1.0 / 3.0),
+            s_basis: basis(2.0 / 3.0),
+
+            orientation,
+            handedness,
+
+            vector: PhantomData,
+        }
+    }
+
+    // methods
+    /// Converts `self` to use the scalar type `G`, rounding to the nearest representable value.
+    /// The vector type changes to `W`.
+    pub fn cast<G: HexFloat, W: HexVector2<G>>(&self) -> HexField2<G, W> {
+        HexField2 {
+            origin: self.origin.cast(),
+            size: self.size.cast(),
+
+            q_basis: self.q_basis.cast(),
+            r_basis: self.r_basis.cast(),
+            s_basis: self.s_basis.cast(),
+
+            orientation: self.orientation,
+            handedness: self.handedness,
+
+            vector: PhantomData,
+        }
+    }
+
+    /// Returns the exact, fractional coordinates of a given position.
+    pub fn get_hex_coord_fraction(&self, position: V) -> HexCoordFraction<F> {
+        self.hex_coord_fraction(Vector2::from_hex_vector(position))
+    }
+
+    pub(crate) fn hex_coord_fraction(&self, position: Vector2<F>) -> HexCoordFraction<F> {
+        // position - origin = q * (q_basis - s_basis) + r * (r_basis - s_basis), because s = -(q + r)
+        let relative = position - self.origin;
+        let a = self.q_basis - self.s_basis;
+        let b = self.r_basis - self.s_basis;
+
+        let determinant = a.x * b.y - a.y * b.x;
+        HexCoordFraction::new(
+            (relative.x * b.y - relative.y * b.x) / determinant,
+            (a.x * relative.y - a.y * relative.x) / determinant,
+        )
+    }
+
+    /// Returns the hex coordinates, rounded to the nearest hex, of a given position.
+    pub fn get_hex_coord(&self, position: V) -> HexCoord {
+        self.get_hex_coord_fraction(position).round()
+    }
+
+    /// Returns the position of a hex coordinate.
+    pub fn get_position<T: Into<HexCoordFraction<F>>>(&self, coord: T) -> V {
+        self.position(coord.into()).to_hex_vector()
+    }
+
+    pub(crate) fn position(&self, coord: HexCoordFraction<F>) -> Vector2<F> {
+        self.origin + self.q_basis * coord.q() + self.r_basis * coord.r() + self.s_basis * coord.s()
+    }
+
+    /// Returns the position of the `i`th vertex of the hex at the given coordinates, moved toward the center of the hex by `scale`.
+    pub fn get_face_vertex_position<C: Into<HexCorner>>(&self, face: HexCoord, scale: F, i: C) -> V {
+        let center = self.position(face.into());
+        let outer = self.position(face.get_vertex(i).into());
+        (center + (outer - center) * scale).to_hex_vector()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn pixel_round_trips() {
+        for orientation in [HexOrientation::FlatTop, HexOrientation::PointyTop] {
+            for handedness in [Handedness::RightHanded, Handedness::LeftHanded] {
+                let field: HexField2<f32, [f32; 2]> = HexField2::new([320.0, 240.0], [24.0, 16.0], orientation, handedness);
+                for hex in HexRangeIterator::new(4, HexTransform::IDENTITY) {
+                    let position = field.get_position(hex);
+                    assert_eq!(field.get_hex_coord(position), hex);
+
+                    let corner = field.get_face_vertex_position(hex, 0.9, 2);
+                    assert_eq!(field.get_hex_coord(corner), hex);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn y_down_mirrors_y_up() {
+        let up: HexField2<f32, [f32; 2]> = HexField2::new([0.0, 0.0], [10.0, 10.0], HexOrientation::FlatTop, Handedness::RightHanded);
+        let down: HexField2<f32, [f32; 2]> = HexField2::new([0.0, 0.0], [10.0, 10.0], HexOrientation::FlatTop, Handedness::LeftHanded);
+
+        let hex = HexCoord::new(0, 1);
+        assert!(up.get_position(hex)[1] > 0.0);
+        assert!(down.get_position(hex)[1] < 0.0);
+        assert!((up.get_position(hex)[1] + down.get_position(hex)[1]).abs() < 1e-4);
+        assert!((up.get_position(hex)[1] - 10.0 * f32::sqrt(3.0)).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,72 @@
+use num_traits::Float;
+use std::fmt::Debug;
+
+/// A floating-point type that `HexCoordFraction`, `HexField`, and `HexField2` can use for their calculations.
+/// Implemented for `f32` and `f64`; use `f64` for fields that extend far from their origin, where `f32` rounding can select the wrong hex.
+pub trait HexFloat: Float + Debug + 'static {
+    /// Zero, usable in constants.
+    const ZERO: Self;
+
+    /// Converts from `f64`, rounding to the nearest representable value.
+    fn from_f64(value: f64) -> Self;
+
+    /// Converts from `i32`, rounding to the nearest representable value.
+    fn from_i32(value: i32) -> Self;
+
+    /// Converts to `f64`.
+    fn as_f64(self) -> f64;
+
+    /// Converts to `i32`, truncating toward zero and saturating at the bounds of `i32`.
+    fn as_i32(self) -> i32;
+
+    /// Converts to `i64`, truncating toward zero and saturating at the bounds of `i64`.
+    fn as_i64(self) -> i64;
+}
+
+impl HexFloat for f32 {
+    const ZERO: Self = 0.0;
+
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+
+    fn from_i32(value: i32) -> Self {
+        value as f32
+    }
+
+    fn as_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn as_i32(self) -> i32 {
+        self as i32
+    }
+
+    fn as_i64(self) -> i64 {
+        self as i64
+    }
+}
+
+impl HexFloat for f64 {
+    const ZERO: Self = 0.0;
+
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
+    fn from_i32(value: i32) -> Self {
+        value as f64
+    }
+
+    fn as_f64(self) -> f64 {
+        self
+    }
+
+    fn as_i32(self) -> i32 {
+        self as i32
+    }
+
+    fn as_i64(self) -> i64 {
+        self as i64
+    }
+}
@@ -1,6 +1,8 @@
 use crate::*;
+use std::{fmt, str::FromStr};
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HexHalfEdge {
     source: HexVertex,
     destination: HexVertex,
@@ -95,10 +97,37 @@ impl HexHalfEdge {
         }
     }
 
+    /// Returns the hexes that touch only the source and only the destination of `self`, respectively.
+    /// They are diagonal to each other, across the two hexes that share `self`.
+    pub fn diagonal_hexes(&self) -> (HexCoord, HexCoord) {
+        (self.prev().twin().hex(), self.next().twin().hex())
+    }
+
     pub fn translate(&self, translation: &HexCoord) -> HexHalfEdge {
         HexHalfEdge::new(
             self.source.translate(translation),
             self.destination.translate(translation),
         )
     }
-}
\ No newline at end of file
+}
+
+/// Writes the edge as its source and destination vertices, such as `(2/3, -1/3, -1/3) -> (1/3, 1/3, -2/3)`.
+impl fmt::Display for HexHalfEdge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} -> {}", self.source(), self.destination())
+    }
+}
+
+/// Parses an edge in the format written by `Display`; the source and destination must be adjacent vertices.
+impl FromStr for HexHalfEdge {
+    type Err = HexParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (source, destination) = s.split_once("->").ok_or_else(|| HexParseError::InvalidFormat(s.to_string()))?;
+        let (source, destination): (HexVertex, HexVertex) = (source.parse()?, destination.parse()?);
+        (0..3)
+            .map(|i| source.get_outgoing_edge(i))
+            .find(|edge| edge.destination() == destination)
+            .ok_or_else(|| HexParseError::NotOnGrid(s.to_string()))
+    }
+}
@@ -0,0 +1,261 @@
+use crate::*;
+use crate::hex_vector::{HexVector3, Vector3};
+use std::collections::HashMap;
+
+/// Triangle mesh data generated by `HexMeshBuilder`, stored as plain vectors so it can be uploaded to any engine.
+/// `positions`, `normals`, and `uvs` have one entry per vertex; every three entries of `indices` form a triangle.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct HexMesh<F: HexFloat = f32> {
+    pub positions: Vec<[F; 3]>,
+    pub normals: Vec<[F; 3]>,
+    pub uvs: Vec<[F; 2]>,
+    pub indices: Vec<u32>,
+}
+
+impl<F: HexFloat> HexMesh<F> {
+    /// Returns the number of vertices in `self`.
+    pub fn vertex_count(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns the number of triangles in `self`.
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+
+    fn push_vertex(&mut self, position: Vector3<F>, normal: Vector3<F>, uv: [F; 2]) -> u32 {
+        self.positions.push([position.x, position.y, position.z]);
+        self.normals.push([normal.x, normal.y, normal.z]);
+        self.uvs.push(uv);
+        (self.positions.len() - 1) as u32
+    }
+}
+
+/// Generates a `HexMesh` for a set of hexes on a `HexField`, each with its own height above the field's plane.
+///
+/// Each hex gets a flat top made of six triangles fanned around its center.
+/// Triangles are wound CCW when viewed from the side their normals face, for both right-handed and left-handed fields; tops face along the field's normal.
+///
+/// By default, every hex top has its own vertices, with UVs mapping the hex into the unit square.
+/// With `shared_vertices`, hex tops at the same height share corner vertices, and UVs are the field's planar coordinates, as returned by `project_onto_plane`, divided by its size.
+///
+/// With `side_walls`, a wall is extruded down from each edge of a hex to any lower neighbor.
+/// Walls have their own vertices; their UVs run from 0 to 1 along the edge and measure height, divided by the field's size, vertically.
+pub struct HexMeshBuilder<'a, F: HexFloat, V: HexVector3<F>> {
+    field: &'a HexField<F, V>,
+    shared_vertices: bool,
+    side_walls: bool,
+    boundary_height: Option<F>,
+}
+
+impl<'a, F: HexFloat, V: HexVector3<F>> HexMeshBuilder<'a, F, V> {
+    // constructor
+    /// Creates a builder that generates flat hex tops on `field` with per-face vertices and no side walls.
+    pub fn new(field: &'a HexField<F, V>) -> HexMeshBuilder<'a, F, V> {
+        HexMeshBuilder {
+            field,
+            shared_vertices: false,
+            side_walls: false,
+            boundary_height: None,
+        }
+    }
+
+    // options
+    /// Sets whether hex tops at the same height share corner vertices instead of each having their own.
+    pub fn shared_vertices(mut self, shared_vertices: bool) -> Self {
+        self.shared_vertices = shared_vertices;
+        self
+    }
+
+    /// Sets whether walls are extruded between hexes at different heights.
+    pub fn side_walls(mut self, side_walls: bool) -> Self {
+        self.side_walls = side_walls;
+        self
+    }
+
+    /// Sets the height assumed for hexes missing from the mesh when extruding side walls, or `None` to leave the mesh's boundary open.
+    pub fn boundary_height(mut self, boundary_height: Option<F>) -> Self {
+        self.boundary_height = boundary_height;
+        self
+    }
+
+    // methods
+    /// Generates a mesh for `hexes`, given as pairs of hex coordinates and heights above the field's plane.
+    /// If a hex appears more than once, its last height is used.
+    pub fn build<I: IntoIterator<Item = (HexCoord, F)>>(&self, hexes: I) -> HexMesh<F> {
+        let mut heights = HashMap::new();
+        let mut order = Vec::new();
+        for (hex, height) in hexes {
+            if heights.insert(hex, height).is_none() {
+                order.push(hex);
+            }
+        }
+
+        let mut mesh = HexMesh {
+            positions: Vec::new(),
+            normals: Vec::new(),
+            uvs: Vec::new(),
+            indices: Vec::new(),
+        };
+        let mut shared_corners = HashMap::new();
+
+        for hex in &order {
+            self.add_top(&mut mesh, &mut shared_corners, *hex, heights[hex]);
+        }
+
+        if self.side_walls {
+            for hex in &order {
+                for edge in hex.edges() {
+                    let neighbor_height = heights.get(&edge.twin().hex()).copied().or(self.boundary_height);
+                    match neighbor_height {
+                        Some(neighbor_height) if neighbor_height < heights[hex] => {
+                            self.add_wall(&mut mesh, edge, heights[hex], neighbor_height);
+                        },
+                        _ => {},
+                    }
+                }
+            }
+        }
+
+        mesh
+    }
+
+    fn add_top(&self, mesh: &mut HexMesh<F>, shared_corners: &mut HashMap<(HexVertex, u64), u32>, hex: HexCoord, height: F) {
+        let normal = Vector3::from_hex_vector(self.field.z_basis());
+        let center = self.field.position_with_height(hex.into(), height);
+        let center_index = mesh.push_vertex(center, normal, self.top_uv(hex, center));
+
+        let corners: Vec<u32> = hex.vertices()
+            .map(|vertex| {
+                let position = self.field.position_with_height(vertex.into(), height);
+                if self.shared_vertices {
+                    *shared_corners.entry((vertex, height.as_f64().to_bits()))
+                        .or_insert_with(|| mesh.push_vertex(position, normal, self.top_uv(hex, position)))
+                } else {
+                    mesh.push_vertex(position, normal, self.top_uv(hex, position))
+                }
+            })
+            .collect();
+
+        for i in 0..6 {
+            self.add_triangle(mesh, [center_index, corners[i], corners[(i + 1) % 6]]);
+        }
+    }
+
+    fn add_wall(&self, mesh: &mut HexMesh<F>, edge: HexHalfEdge, top: F, bottom: F) {
+        let outward = self.field.position(edge.twin().hex().into()) - self.field.position(edge.hex().into());
+        let normal = outward.normalize();
+
+        let size = self.field.size();
+        let (zero, one) = (F::zero(), F::one());
+        let source_top = mesh.push_vertex(self.field.position_with_height(edge.source().into(), top), normal, [zero, top / size]);
+        let destination_top = mesh.push_vertex(self.field.position_with_height(edge.destination().into(), top), normal, [one, top / size]);
+        let destination_bottom = mesh.push_vertex(self.field.position_with_height(edge.destination().into(), bottom), normal, [one, bottom / size]);
+        let source_bottom = mesh.push_vertex(self.field.position_with_height(edge.source().into(), bottom), normal, [zero, bottom / size]);
+
+        // seen from outside, the edge runs in the same rotational direction as the top's triangles
+        self.add_triangle(mesh, [source_bottom, destination_bottom, destination_top]);
+        self.add_triangle(mesh, [source_bottom, destination_top, source_top]);
+    }
+
+    // adds a triangle given in the field's vertex numbering order, which is CCW seen from the normal only in a right-handed field
+    fn add_triangle(&self, mesh: &mut HexMesh<F>, [a, b, c]: [u32; 3]) {
+        match self.field.handedness() {
+            Handedness::RightHanded => mesh.indices.extend([a, b, c]),
+            Handedness::LeftHanded => mesh.indices.extend([a, c, b]),
+        }
+    }
+
+    fn top_uv(&self, hex: HexCoord, position: Vector3<F>) -> [F; 2] {
+        let x_basis = Vector3::from_hex_vector(self.field.x_basis());
+        let y_basis = Vector3::from_hex_vector(self.field.y_basis());
+        let size = self.field.size();
+
+        if self.shared_vertices {
+            let planar = position - Vector3::from_hex_vector(self.field.origin());
+            [planar.dot(x_basis) / size, planar.dot(y_basis) / size]
+        } else {
+            let half = F::from_f64(0.5);
+            let offset = (position - self.field.position(hex.into())) / (size * F::from_f64(2.0));
+            [offset.dot(x_basis) + half, offset.dot(y_basis) + half]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn layout(handedness: Handedness) -> HexField<f32, [f32; 3]> {
+        HexField::with_layout([3.0, -2.0, 0.5], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0], HexOrientation::FlatTop, handedness, 1.0)
+    }
+
+    // every triangle's winding agrees with its vertices' normals
+    fn assert_winding_matches_normals(mesh: &HexMesh) {
+        for triangle in 0..mesh.triangle_count() {
+            let geometric = triangle_normal(mesh, triangle);
+            let normal = mesh.normals[mesh.indices[3 * triangle] as usize];
+            let dot: f32 = (0..3).map(|i| geometric[i] * normal[i]).sum();
+            assert!(dot > 0.0);
+        }
+    }
+
+    fn triangle_normal(mesh: &HexMesh, triangle: usize) -> [f32; 3] {
+        let [a, b, c] = [0, 1, 2].map(|i| mesh.positions[mesh.indices[3 * triangle + i] as usize]);
+        let u = [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+        let v = [c[0] - a[0], c[1] - a[1], c[2] - a[2]];
+        [u[1] * v[2] - u[2] * v[1], u[2] * v[0] - u[0] * v[2], u[0] * v[1] - u[1] * v[0]]
+    }
+
+    #[test]
+    fn flat_tops() {
+        for handedness in [Handedness::RightHanded, Handedness::LeftHanded] {
+            let field = layout(handedness);
+            let hexes = [(HexCoord::ZERO, 0.0), (HexCoord::new(1, 0), 0.0)];
+
+            let per_face = HexMeshBuilder::new(&field).build(hexes);
+            assert_eq!(per_face.vertex_count(), 14);
+            assert_eq!(per_face.triangle_count(), 12);
+            assert_eq!(per_face.uvs[0], [0.5, 0.5]);
+            for uv in &per_face.uvs {
+                assert!(uv.iter().all(|x| (0.0..=1.0).contains(x)));
+            }
+
+            // the two hexes share an edge, so two corners are welded
+            let shared = HexMeshBuilder::new(&field).shared_vertices(true).build(hexes);
+            assert_eq!(shared.vertex_count(), 12);
+            assert_eq!(shared.triangle_count(), 12);
+            assert_eq!(shared.uvs[0], [0.0, 0.0]);
+
+            for mesh in [&per_face, &shared] {
+                assert_winding_matches_normals(mesh);
+                assert!(mesh.normals.iter().all(|normal| *normal == [0.0, 0.0, 1.0]));
+
+                // each hex's center lies where the field puts the hex
+                assert_eq!(mesh.positions[0], field.get_position(HexCoord::ZERO));
+                assert_eq!(field.get_hex_coord(mesh.positions[0]), HexCoord::ZERO);
+            }
+        }
+    }
+
+    #[test]
+    fn side_walls() {
+        for handedness in [Handedness::RightHanded, Handedness::LeftHanded] {
+            let field = layout(handedness);
+            let hexes = [(HexCoord::ZERO, 2.0), (HexCoord::new(1, 0), 1.0), (HexCoord::new(-1, 0), 3.0)];
+
+            let builder = HexMeshBuilder::new(&field).side_walls(true);
+            let mesh = builder.build(hexes);
+            // one wall from the middle hex down to the right, and one from the left hex down to the middle
+            assert_eq!(mesh.triangle_count(), 18 + 4);
+            assert_winding_matches_normals(&mesh);
+            for triangle in 18..mesh.triangle_count() {
+                assert!(mesh.normals[mesh.indices[3 * triangle] as usize][2].abs() < 1e-6);
+            }
+
+            let closed = builder.boundary_height(Some(0.0)).build(hexes);
+            assert_eq!(closed.triangle_count(), 18 + 4 + 2 * 14);
+            assert_winding_matches_normals(&closed);
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use crate::*;
+
+/// Which rows or columns an `OffsetCoord` shoves over, following Amit Patel's [offset coordinates](https://www.redblobgames.com/grids/hexagons/#coordinates-offset).
+/// The `R` layouts store pointy-top hexes in rows, and the `Q` layouts store flat-top hexes in columns.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum OffsetLayout {
+    /// Odd rows are shoved right by half a hex.
+    OddR,
+    /// Even rows are shoved right by half a hex.
+    EvenR,
+    /// Odd columns are shoved down by half a hex.
+    OddQ,
+    /// Even columns are shoved down by half a hex.
+    EvenQ,
+}
+
+/// A hex position in offset coordinates, as used by many tile editors, which store hex maps in rectangular arrays.
+/// Converts losslessly to and from `HexCoord`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct OffsetCoord {
+    col: i32,
+    row: i32,
+    layout: OffsetLayout,
+}
+
+impl OffsetCoord {
+    // accessors
+    pub fn col(&self) -> i32 {
+        self.col
+    }
+
+    pub fn row(&self) -> i32 {
+        self.row
+    }
+
+    pub fn layout(&self) -> OffsetLayout {
+        self.layout
+    }
+
+    // constructors
+    pub fn new(col: i32, row: i32, layout: OffsetLayout) -> OffsetCoord {
+        OffsetCoord {
+            col,
+            row,
+            layout,
+        }
+    }
+
+    /// Returns the offset coordinates of `hex` in the given layout.
+    pub fn from_hex(hex: HexCoord, layout: OffsetLayout) -> OffsetCoord {
+        let (q, r) = (hex.q(), hex.r());
+        match layout {
+            OffsetLayout::OddR => OffsetCoord::new(q + (r - (r & 1)) / 2, r, layout),
+            OffsetLayout::EvenR => OffsetCoord::new(q + (r + (r & 1)) / 2, r, layout),
+            OffsetLayout::OddQ => OffsetCoord::new(q, r + (q - (q & 1)) / 2, layout),
+            OffsetLayout::EvenQ => OffsetCoord::new(q, r + (q + (q & 1)) / 2, layout),
+        }
+    }
+
+    // methods
+    /// Returns the axial coordinates of `self`.
+    pub fn to_hex(&self) -> HexCoord {
+        let (col, row) = (self.col, self.row);
+        match self.layout {
+            OffsetLayout::OddR => HexCoord::new(col - (row - (row & 1)) / 2, row),
+            OffsetLayout::EvenR => HexCoord::new(col - (row + (row & 1)) / 2, row),
+            OffsetLayout::OddQ => HexCoord::new(col, row - (col - (col & 1)) / 2),
+            OffsetLayout::EvenQ => HexCoord::new(col, row - (col + (col & 1)) / 2),
+        }
+    }
+
+    /// Returns the `i`th neighbor of `self`, numbered as in `HexCoord::get_neighbor`, in the same layout.
+    pub fn get_neighbor<D: Into<HexDirection>>(&self, i: D) -> OffsetCoord {
+        OffsetCoord::from_hex(self.to_hex().get_neighbor(i), self.layout)
+    }
+
+    /// Returns an iterator over the neighbors of `self`, in the same layout.
+    pub fn neighbors(&self) -> impl Iterator<Item = OffsetCoord> {
+        let layout = self.layout;
+        self.to_hex().neighbors().map(move |hex| OffsetCoord::from_hex(hex, layout))
+    }
+}
+
+impl From<OffsetCoord> for HexCoord {
+    fn from(coord: OffsetCoord) -> Self {
+        coord.to_hex()
+    }
+}
+
+/// Which axis a `DoubledCoord` doubles, following Amit Patel's [doubled coordinates](https://www.redblobgames.com/grids/hexagons/#coordinates-doubled).
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum DoubledLayout {
+    /// Columns step by two between horizontal neighbors, for pointy-top hexes.
+    DoubledWidth,
+    /// Rows step by two between vertical neighbors, for flat-top hexes.
+    DoubledHeight,
+}
+
+/// A hex position in doubled coordinates, where `col + row` is always even.
+/// Converts losslessly to and from `HexCoord`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct DoubledCoord {
+    col: i32,
+    row: i32,
+    layout: DoubledLayout,
+}
+
+impl DoubledCoord {
+    // accessors
+    pub fn col(&self) -> i32 {
+        self.col
+    }
+
+    pub fn row(&self) -> i32 {
+        self.row
+    }
+
+    pub fn layout(&self) -> DoubledLayout {
+        self.layout
+    }
+
+    // constructors
+    /// Creates a `DoubledCoord`, or returns `None` if `col + row` is odd, which doesn't correspond to a hex.
+    pub fn new(col: i32, row: i32, layout: DoubledLayout) -> Option<DoubledCoord> {
+        if (col + row).rem_euclid(2) == 0 {
+            Some(DoubledCoord {
+                col,
+                row,
+                layout,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the doubled coordinates of `hex` in the given layout.
+    pub fn from_hex(hex: HexCoord, layout: DoubledLayout) -> DoubledCoord {
+        let (q, r) = (hex.q(), hex.r());
+        let (col, row) = match layout {
+            DoubledLayout::DoubledWidth => (2 * q + r, r),
+            DoubledLayout::DoubledHeight => (q, 2 * r + q),
+        };
+
+        DoubledCoord {
+            col,
+            row,
+            layout,
+        }
+    }
+
+    // methods
+    /// Returns the axial coordinates of `self`.
+    pub fn to_hex(&self) -> HexCoord {
+        match self.layout {
+            DoubledLayout::DoubledWidth => HexCoord::new((self.col - self.row) / 2, self.row),
+            DoubledLayout::DoubledHeight => HexCoord::new(self.col, (self.row - self.col) / 2),
+        }
+    }
+
+    /// Returns the `i`th neighbor of `self`, numbered as in `HexCoord::get_neighbor`, in the same layout.
+    pub fn get_neighbor<D: Into<HexDirection>>(&self, i: D) -> DoubledCoord {
+        DoubledCoord::from_hex(self.to_hex().get_neighbor(i), self.layout)
+    }
+
+    /// Returns an iterator over the neighbors of `self`, in the same layout.
+    pub fn neighbors(&self) -> impl Iterator<Item = DoubledCoord> {
+        let layout = self.layout;
+        self.to_hex().neighbors().map(move |hex| DoubledCoord::from_hex(hex, layout))
+    }
+}
+
+impl From<DoubledCoord> for HexCoord {
+    fn from(coord: DoubledCoord) -> Self {
+        coord.to_hex()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn offset_round_trips() {
+        for layout in [OffsetLayout::OddR, OffsetLayout::EvenR, OffsetLayout::OddQ, OffsetLayout::EvenQ] {
+            for hex in HexRangeIterator::new(5, HexTransform::IDENTITY) {
+                let offset = OffsetCoord::from_hex(hex, layout);
+                assert_eq!(offset.to_hex(), hex);
+                assert_eq!(OffsetCoord::new(offset.col(), offset.row(), layout).to_hex(), hex);
+            }
+        }
+    }
+
+    #[test]
+    fn offset_neighbors_match_tables() {
+        let neighbors = |coord: OffsetCoord| -> HashSet<(i32, i32)> {
+            coord.neighbors().map(|neighbor| (neighbor.col() - coord.col(), neighbor.row() - coord.row())).collect()
+        };
+
+        // odd rows of odd-r and even columns of even-q, from Amit Patel's neighbor tables
+        let odd_row: HashSet<(i32, i32)> = [(1, 0), (1, -1), (0, -1), (-1, 0), (0, 1), (1, 1)].into();
+        assert_eq!(neighbors(OffsetCoord::new(3, -1, OffsetLayout::OddR)), odd_row);
+        let even_col: HashSet<(i32, i32)> = [(1, 1), (1, 0), (0, -1), (-1, 0), (-1, 1), (0, 1)].into();
+        assert_eq!(neighbors(OffsetCoord::new(-2, 5, OffsetLayout::EvenQ)), even_col);
+
+        let coord = OffsetCoord::new(4, 7, OffsetLayout::EvenR);
+        assert_eq!(coord.get_neighbor(2).to_hex(), coord.to_hex().get_neighbor(2));
+    }
+
+    #[test]
+    fn doubled_round_trips() {
+        for layout in [DoubledLayout::DoubledWidth, DoubledLayout::DoubledHeight] {
+            for hex in HexRangeIterator::new(5, HexTransform::IDENTITY) {
+                let doubled = DoubledCoord::from_hex(hex, layout);
+                assert_eq!(HexCoord::from(doubled), hex);
+                assert_eq!(DoubledCoord::new(doubled.col(), doubled.row(), layout), Some(doubled));
+                assert!(doubled.neighbors().all(|neighbor| HexCoord::hex_distance(neighbor.to_hex(), hex) == 1));
+            }
+        }
+
+        assert_eq!(DoubledCoord::new(1, 2, DoubledLayout::DoubledWidth), None);
+        let east = DoubledCoord::new(0, 0, DoubledLayout::DoubledWidth).unwrap().neighbors().find(|n| n.row() == 0 && n.col() > 0);
+        assert_eq!(east.map(|n| n.col()), Some(2));
+    }
+}
@@ -0,0 +1,320 @@
+use crate::*;
+use std::collections::{HashMap, HashSet};
+
+/// Options controlling `HexShape::pack`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct HexPackingOptions {
+    /// The rotations, in sixth-turns CCW, with which pieces may be placed.
+    pub rotations: Vec<i32>,
+    /// The maximum number of solutions to return, or `None` to return every solution.
+    pub solution_limit: Option<usize>,
+}
+
+impl HexPackingOptions {
+    /// Creates options that allow every rotation and return every solution.
+    pub fn new() -> HexPackingOptions {
+        HexPackingOptions {
+            rotations: (0..6).collect(),
+            solution_limit: None,
+        }
+    }
+}
+
+impl Default for HexPackingOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// a single way of placing a piece within the region
+struct Placement {
+    piece: usize,
+    transform: HexTransform,
+    cells: Vec<HexCoord>,
+}
+
+impl HexShape {
+    /// Finds ways to tile `self` exactly with `pieces`, using each piece exactly once.
+    /// Each solution contains one transform per piece, in the same order as `pieces`; `pieces[i].transformed(solution[i])` gives the placed piece.
+    /// Solutions that differ only by swapping interchangeable pieces are reported once.
+    ///
+    /// Uses Knuth's Algorithm X with dancing links.
+    pub fn pack(&self, pieces: &[HexShape], options: &HexPackingOptions) -> Vec<Vec<HexTransform>> {
+        let region = Self::distinct_hexes(self);
+        let cell_index: HashMap<HexCoord, usize> = region.iter().enumerate().map(|(i, hex)| (*hex, i)).collect();
+
+        let piece_area: usize = pieces.iter().map(|piece| Self::distinct_hexes(piece).len()).sum();
+        if piece_area != region.len() || options.solution_limit == Some(0) {
+            return Vec::new();
+        }
+
+        // enumerate placements, merging rotations and translations that cover the same cells
+        let mut placements = Vec::new();
+        let mut placement_sets = Vec::new();
+        for (piece_index, piece) in pieces.iter().enumerate() {
+            let mut seen = HashSet::new();
+            for rotation in &options.rotations {
+                let rotated = piece.rotated(*rotation);
+                let mut cells: Vec<HexCoord> = (0..rotated.len()).filter_map(|i| rotated.get(i)).collect();
+                cells.sort();
+                cells.dedup();
+
+                let anchor = match cells.first() {
+                    Some(anchor) => *anchor,
+                    None => continue,
+                };
+                for target in &region {
+                    let translation = *target - anchor;
+                    let placed: Vec<HexCoord> = cells.iter().map(|cell| *cell + translation).collect();
+                    if placed.iter().all(|cell| cell_index.contains_key(cell)) && seen.insert(placed.clone()) {
+                        placements.push(Placement {
+                            piece: piece_index,
+                            transform: HexTransform::new(translation, *rotation),
+                            cells: placed,
+                        });
+                    }
+                }
+            }
+
+            let mut set: Vec<Vec<HexCoord>> = seen.into_iter().collect();
+            set.sort();
+            placement_sets.push(set);
+        }
+
+        // pieces with identical placement sets are interchangeable
+        let groups: Vec<usize> = (0..pieces.len())
+            .map(|i| (0..=i).find(|j| placement_sets[*j] == placement_sets[i]).unwrap())
+            .collect();
+
+        let rows: Vec<Vec<usize>> = placements.iter()
+            .map(|placement| {
+                let mut columns = vec![placement.piece];
+                columns.extend(placement.cells.iter().map(|cell| pieces.len() + cell_index[cell]));
+                columns
+            })
+            .collect();
+
+        let mut links = DancingLinks::new(pieces.len() + region.len(), &rows);
+        let mut solutions = Vec::new();
+        let mut found = HashSet::new();
+        links.search(&mut Vec::new(), &mut |chosen: &[usize]| {
+            let mut key: Vec<(usize, Vec<HexCoord>)> = chosen.iter()
+                .map(|row| (groups[placements[*row].piece], placements[*row].cells.clone()))
+                .collect();
+            key.sort();
+
+            if found.insert(key) {
+                let mut solution = vec![HexTransform::IDENTITY; pieces.len()];
+                for row in chosen {
+                    solution[placements[*row].piece] = placements[*row].transform;
+                }
+                solutions.push(solution);
+            }
+
+            options.solution_limit.map_or(true, |limit| solutions.len() < limit)
+        });
+
+        solutions
+    }
+
+    fn distinct_hexes(shape: &HexShape) -> Vec<HexCoord> {
+        let mut hexes: Vec<HexCoord> = shape.into_iter().copied().collect();
+        hexes.sort();
+        hexes.dedup();
+        hexes
+    }
+}
+
+// Knuth's dancing links representation of a sparse exact cover matrix; node 0 is the root and nodes 1..=columns are column headers
+struct DancingLinks {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    column: Vec<usize>,
+    row: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl DancingLinks {
+    fn new(columns: usize, rows: &[Vec<usize>]) -> DancingLinks {
+        let mut links = DancingLinks {
+            left: (0..=columns).map(|i| if i == 0 { columns } else { i - 1 }).collect(),
+            right: (0..=columns).map(|i| if i == columns { 0 } else { i + 1 }).collect(),
+            up: (0..=columns).collect(),
+            down: (0..=columns).collect(),
+            column: (0..=columns).collect(),
+            row: vec![usize::MAX; columns + 1],
+            size: vec![0; columns + 1],
+        };
+
+        for (row_index, row) in rows.iter().enumerate() {
+            let first = links.left.len();
+            for (i, column) in row.iter().enumerate() {
+                let header = column + 1;
+                let node = links.left.len();
+
+                links.left.push(if i == 0 { node } else { node - 1 });
+                links.right.push(first);
+                links.up.push(links.up[header]);
+                links.down.push(header);
+                links.column.push(header);
+                links.row.push(row_index);
+
+                let above = links.up[header];
+                links.down[above] = node;
+                links.up[header] = node;
+                links.size[header] += 1;
+
+                if i > 0 {
+                    links.right[node - 1] = node;
+                    links.left[first] = node;
+                }
+            }
+        }
+
+        links
+    }
+
+    fn cover(&mut self, header: usize) {
+        self.right[self.left[header]] = self.right[header];
+        self.left[self.right[header]] = self.left[header];
+
+        let mut i = self.down[header];
+        while i != header {
+            let mut j = self.right[i];
+            while j != i {
+                self.down[self.up[j]] = self.down[j];
+                self.up[self.down[j]] = self.up[j];
+                self.size[self.column[j]] -= 1;
+                j = self.right[j];
+            }
+            i = self.down[i];
+        }
+    }
+
+    fn uncover(&mut self, header: usize) {
+        let mut i = self.up[header];
+        while i != header {
+            let mut j = self.left[i];
+            while j != i {
+                self.size[self.column[j]] += 1;
+                self.down[self.up[j]] = j;
+                self.up[self.down[j]] = j;
+                j = self.left[j];
+            }
+            i = self.up[i];
+        }
+
+        self.right[self.left[header]] = header;
+        self.left[self.right[header]] = header;
+    }
+
+    // calls `on_solution` with the rows of each exact cover; returns false once `on_solution` asks to stop
+    fn search<F: FnMut(&[usize]) -> bool>(&mut self, chosen: &mut Vec<usize>, on_solution: &mut F) -> bool {
+        if self.right[0] == 0 {
+            return on_solution(chosen);
+        }
+
+        // choose the column with the fewest remaining rows
+        let mut header = self.right[0];
+        let mut i = self.right[header];
+        while i != 0 {
+            if self.size[i] < self.size[header] {
+                header = i;
+            }
+            i = self.right[i];
+        }
+
+        self.cover(header);
+        let mut keep_going = true;
+        let mut r = self.down[header];
+        while r != header && keep_going {
+            chosen.push(self.row[r]);
+            let mut j = self.right[r];
+            while j != r {
+                self.cover(self.column[j]);
+                j = self.right[j];
+            }
+
+            keep_going = self.search(chosen, on_solution);
+
+            let mut j = self.left[r];
+            while j != r {
+                self.uncover(self.column[j]);
+                j = self.left[j];
+            }
+            chosen.pop();
+            r = self.down[r];
+        }
+        self.uncover(header);
+
+        keep_going
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn shape(coords: &[(i32, i32)]) -> HexShape {
+        HexShape::new_from_vec(coords.iter().map(|(q, r)| HexCoord::new(*q, *r)).collect())
+    }
+
+    fn assert_tiles(region: &HexShape, pieces: &[HexShape], solution: &[HexTransform]) {
+        let mut covered = Vec::new();
+        for (piece, transform) in pieces.iter().zip(solution) {
+            let view = piece.transformed(*transform);
+            covered.extend((0..view.len()).filter_map(|i| view.get(i)));
+        }
+        covered.sort();
+
+        let mut expected: Vec<HexCoord> = region.into_iter().copied().collect();
+        expected.sort();
+        assert_eq!(covered, expected);
+    }
+
+    #[test]
+    fn hexagon_ring_tiles_with_pairs() {
+        let region = HexShape::new_from_vec(HexCoord::new(3, -2).neighbors().collect());
+        let pieces = vec![shape(&[(0, 0), (1, 0)]); 3];
+
+        let solutions = region.pack(&pieces, &HexPackingOptions::new());
+        assert_eq!(solutions.len(), 2);
+        for solution in &solutions {
+            assert_tiles(&region, &pieces, solution);
+        }
+    }
+
+    #[test]
+    fn rotations_and_limits_are_respected() {
+        let region = shape(&[(0, 0), (0, 1), (5, 5), (5, 6)]);
+        let pieces = vec![shape(&[(0, 0), (1, 0)]); 2];
+
+        let unrotated = HexPackingOptions {
+            rotations: vec![0],
+            solution_limit: None,
+        };
+        assert!(region.pack(&pieces, &unrotated).is_empty());
+
+        let solutions = region.pack(&pieces, &HexPackingOptions::new());
+        assert_eq!(solutions.len(), 1);
+        assert_tiles(&region, &pieces, &solutions[0]);
+
+        let hexagon = HexShape::new_hexagon(1);
+        let limited = HexPackingOptions {
+            rotations: (0..6).collect(),
+            solution_limit: Some(1),
+        };
+        let arc = shape(&[(1, -1), (1, 0), (0, 1)]);
+        let pieces = vec![shape(&[(0, 0)]), arc.clone(), arc];
+        assert_eq!(hexagon.pack(&pieces, &limited).len(), 1);
+
+        // three with the single hex in the middle, and one for each other position of the single hex
+        let solutions = hexagon.pack(&pieces, &HexPackingOptions::new());
+        assert_eq!(solutions.len(), 9);
+        for solution in &solutions {
+            assert_tiles(&hexagon, &pieces, solution);
+        }
+    }
+}
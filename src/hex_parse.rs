@@ -0,0 +1,94 @@
+use std::fmt;
+
+/// An error from parsing a hex type from a string with `FromStr`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum HexParseError {
+    /// The text isn't shaped like the type being parsed, such as a coordinate with the wrong number of components.
+    InvalidFormat(String),
+    /// A component isn't a valid number.
+    InvalidNumber(String),
+    /// The *q*, *r*, and *s* components don't sum to zero.
+    NonzeroSum(String),
+    /// The coordinates are well-formed, but don't name a vertex or an edge of the hex grid.
+    NotOnGrid(String),
+}
+
+impl fmt::Display for HexParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HexParseError::InvalidFormat(text) => write!(f, "invalid hex format: \"{}\"", text),
+            HexParseError::InvalidNumber(text) => write!(f, "invalid number in hex coordinates: \"{}\"", text),
+            HexParseError::NonzeroSum(text) => write!(f, "hex coordinates don't sum to zero: \"{}\"", text),
+            HexParseError::NotOnGrid(text) => write!(f, "not a vertex or edge of the hex grid: \"{}\"", text),
+        }
+    }
+}
+
+impl std::error::Error for HexParseError {}
+
+// parses "(q, r, s)", "(q, r)", "q, r, s", or "q, r", with each component parsed by `number`, returning q and r
+pub(crate) fn parse_components<N: Fn(&str) -> Option<i32>>(text: &str, number: N) -> Result<(i32, i32), HexParseError> {
+    let trimmed = text.trim();
+    let inner = match (trimmed.strip_prefix('('), trimmed.strip_suffix(')')) {
+        (Some(_), Some(_)) => &trimmed[1..trimmed.len() - 1],
+        (None, None) => trimmed,
+        _ => return Err(HexParseError::InvalidFormat(text.to_string())),
+    };
+
+    let components = inner.split(',')
+        .map(|component| number(component.trim()).ok_or_else(|| HexParseError::InvalidNumber(component.trim().to_string())))
+        .collect::<Result<Vec<i32>, HexParseError>>()?;
+
+    match components[..] {
+        [q, r] => Ok((q, r)),
+        [q, r, s] if q.checked_add(r).and_then(|sum| sum.checked_add(s)) == Some(0) => Ok((q, r)),
+        [_, _, _] => Err(HexParseError::NonzeroSum(text.to_string())),
+        _ => Err(HexParseError::InvalidFormat(text.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn coords_display_and_parse() {
+        let hex = HexCoord::new(1, -2);
+        assert_eq!(hex.to_string(), "(1, -2, 1)");
+        for text in ["(1, -2, 1)", "1,-2", " ( 1 ,-2 ) ", "1, -2, 1"] {
+            assert_eq!(text.parse::<HexCoord>(), Ok(hex));
+        }
+
+        assert_eq!("(1, 2, 3)".parse::<HexCoord>(), Err(HexParseError::NonzeroSum("(1, 2, 3)".to_string())));
+        assert_eq!("1, x".parse::<HexCoord>(), Err(HexParseError::InvalidNumber("x".to_string())));
+        assert_eq!("(1, 2".parse::<HexCoord>(), Err(HexParseError::InvalidFormat("(1, 2".to_string())));
+        assert_eq!("1".parse::<HexCoord>(), Err(HexParseError::InvalidFormat("1".to_string())));
+        assert_eq!("1".parse::<HexCoord>().unwrap_err().to_string(), "invalid hex format: \"1\"");
+    }
+
+    #[test]
+    fn vertices_edges_and_transforms() {
+        let vertex = HexCoord::new(1, -2).get_vertex(0);
+        assert_eq!(vertex.to_string(), "(5/3, -7/3, 2/3)");
+        assert_eq!(vertex.to_string().parse::<HexVertex>(), Ok(vertex));
+        assert_eq!("(1, 0, -1)".parse::<HexVertex>(), Err(HexParseError::NotOnGrid("(1, 0, -1)".to_string())));
+        assert_eq!("(1/3, 2/3, -1)".parse::<HexVertex>(), Err(HexParseError::NotOnGrid("(1/3, 2/3, -1)".to_string())));
+        assert!("(1/2, -1/2)".parse::<HexVertex>().is_err());
+
+        let edge = HexCoord::new(-3, 1).get_half_edge(4);
+        assert_eq!(edge.to_string().parse::<HexHalfEdge>(), Ok(edge));
+        assert!(edge.to_string().contains(" -> "));
+        assert!("(2/3, -1/3, -1/3)".parse::<HexHalfEdge>().is_err());
+        let far = format!("{} -> {}", edge.source(), edge.next().destination());
+        assert_eq!(far.parse::<HexHalfEdge>(), Err(HexParseError::NotOnGrid(far.clone())));
+        let backwards = format!("{} -> {}", edge.source(), edge.source());
+        assert!(backwards.parse::<HexHalfEdge>().is_err());
+
+        let transform = HexTransform::new(HexCoord::new(4, -1), 5);
+        assert_eq!(transform.to_string(), "(4, -1, -3) rot 5");
+        assert_eq!(transform.to_string().parse::<HexTransform>(), Ok(transform));
+        assert_eq!("4,-1 rot 5".parse::<HexTransform>(), Ok(transform));
+        assert!("(4, -1, -3) rot x".parse::<HexTransform>().is_err());
+        assert!("(4, -1, -3)".parse::<HexTransform>().is_err());
+    }
+}
@@ -0,0 +1,124 @@
+use crate::*;
+use std::collections::HashSet;
+
+/// Which transformations are considered when deciding whether two polyhexes are the same.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum PolyhexKind {
+    /// Polyhexes are the same only if they are equal up to translation.
+    Fixed,
+    /// Polyhexes are the same if they are equal up to rotation and translation.
+    OneSided,
+    /// Polyhexes are the same if they are equal up to reflection, rotation, and translation.
+    Free,
+}
+
+impl HexShape {
+    /// Returns every connected shape of `size` hexes, with shapes considered equal according to `kind`.
+    /// Each shape is returned in normalized form: sorted, with its smallest hex at the origin.
+    /// `OneSided` and `Free` shapes are also canonical in the sense of `canonical` and `canonical_with_reflection`, respectively.
+    /// The shapes are returned in sorted order, and a `size` of zero returns no shapes.
+    pub fn polyhexes(size: u32, kind: PolyhexKind) -> Vec<HexShape> {
+        if size == 0 {
+            return Vec::new();
+        }
+
+        let mut fixed = Vec::new();
+        let mut polyhex = Vec::new();
+        let mut seen = HashSet::new();
+        seen.insert(HexCoord::ZERO);
+        Self::redelmeier(size as usize, vec![HexCoord::ZERO], &mut polyhex, &mut seen, &mut fixed);
+
+        let mut result: Vec<HexShape> = match kind {
+            PolyhexKind::Fixed => fixed,
+            PolyhexKind::OneSided => fixed.iter()
+                .map(|shape| shape.canonical().0)
+                .collect::<HashSet<HexShape>>()
+                .into_iter()
+                .collect(),
+            PolyhexKind::Free => fixed.iter()
+                .map(|shape| shape.canonical_with_reflection().0)
+                .collect::<HashSet<HexShape>>()
+                .into_iter()
+                .collect(),
+        };
+
+        result.sort_by(|a, b| a.into_iter().cmp(b));
+        result
+    }
+
+    // Redelmeier's algorithm: grow the polyhex one hex at a time from the origin, only ever adding hexes that come after the
+    // origin in `HexCoord`'s (q, r) order, so each fixed polyhex is generated exactly once with the origin as its smallest hex
+    fn redelmeier(
+        size: usize,
+        mut untried: Vec<HexCoord>,
+        polyhex: &mut Vec<HexCoord>,
+        seen: &mut HashSet<HexCoord>,
+        output: &mut Vec<HexShape>,
+    ) {
+        while let Some(hex) = untried.pop() {
+            polyhex.push(hex);
+
+            if polyhex.len() == size {
+                output.push(HexShape::normalized(polyhex.clone()).0);
+            } else {
+                let new_neighbors: Vec<HexCoord> = hex.neighbors()
+                    .filter(|neighbor| Self::after_origin(*neighbor) && !seen.contains(neighbor))
+                    .collect();
+                seen.extend(new_neighbors.iter().copied());
+
+                let mut next_untried = untried.clone();
+                next_untried.extend(new_neighbors.iter().copied());
+                Self::redelmeier(size, next_untried, polyhex, seen, output);
+
+                for neighbor in &new_neighbors {
+                    seen.remove(neighbor);
+                }
+            }
+
+            polyhex.pop();
+        }
+    }
+
+    fn after_origin(hex: HexCoord) -> bool {
+        hex >= HexCoord::ZERO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn counts(kind: PolyhexKind) -> Vec<usize> {
+        (1..=7).map(|size| HexShape::polyhexes(size, kind).len()).collect()
+    }
+
+    // OEIS A001207
+    #[test]
+    fn fixed_polyhex_counts() {
+        assert_eq!(counts(PolyhexKind::Fixed), vec![1, 3, 11, 44, 186, 814, 3652]);
+    }
+
+    // OEIS A006535
+    #[test]
+    fn one_sided_polyhex_counts() {
+        assert_eq!(counts(PolyhexKind::OneSided), vec![1, 1, 3, 10, 33, 147, 620]);
+    }
+
+    // OEIS A000228
+    #[test]
+    fn free_polyhex_counts() {
+        assert_eq!(counts(PolyhexKind::Free), vec![1, 1, 3, 7, 22, 82, 333]);
+    }
+
+    #[test]
+    fn polyhexes_are_connected_and_distinct() {
+        let shapes = HexShape::polyhexes(5, PolyhexKind::Free);
+        for (i, a) in shapes.iter().enumerate() {
+            assert_eq!(a.len(), 5);
+            assert!(a.is_connected());
+            for b in &shapes[i + 1..] {
+                assert!(!a.is_congruent(b, true));
+            }
+        }
+    }
+}
@@ -123,4 +123,31 @@ impl Iterator for HexNeighborIterator {
             Some(self.hex.get_neighbor(self.i))
         }
     }
-}
\ No newline at end of file
+}
+
+pub struct HexDiagonalIterator {
+    hex: HexCoord,
+    i: i32,
+}
+
+impl HexDiagonalIterator {
+    pub fn new(hex: HexCoord) -> Self {
+        Self {
+            hex,
+            i: -1,
+        }
+    }
+}
+
+impl Iterator for HexDiagonalIterator {
+    type Item = HexCoord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.i += 1;
+        if self.i >= 6 {
+            None
+        } else {
+            Some(self.hex.get_diagonal(self.i))
+        }
+    }
+}
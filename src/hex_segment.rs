@@ -0,0 +1,234 @@
+use crate::*;
+use crate::hex_vector::{HexVector3, Vector3};
+
+/// One hex visited by a `HexSegmentIterator`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct HexSegmentStep<F: HexFloat = f32> {
+    /// The hex the segment passes through.
+    pub hex: HexCoord,
+    /// The segment parameter, from 0 at its start to 1 at its end, where the segment enters `hex`.
+    pub t_enter: F,
+    /// The segment parameter where the segment leaves `hex`, or 1 if it ends inside it.
+    pub t_exit: F,
+    /// The half-edge of `hex` the segment crossed to enter it, or `None` for the hex containing the segment's start.
+    pub entry_edge: Option<HexHalfEdge>,
+}
+
+/// An iterator over the hexes a straight segment passes through, in order from its start to its end.
+/// Created by `HexField::traverse_segment`, or directly from fractional hex coordinates with `new`.
+///
+/// The traversal is exact: consecutive hexes always share the edge the segment crosses.
+/// When the segment passes exactly through a vertex, it steps directly into the hex it continues into.
+/// By default, the third hex at the vertex, which the segment only touches, is not visited; set `touching_corners` to visit it too.
+pub struct HexSegmentIterator<F: HexFloat = f32> {
+    start: HexCoordFraction<F>,
+    direction: HexCoordFraction<F>,
+    touching_corners: bool,
+    corner: Option<HexSegmentStep<F>>,
+    next: Option<HexSegmentStep<F>>,
+}
+
+impl<F: HexFloat> HexSegmentIterator<F> {
+    /// Creates an iterator over the hexes the segment from `start` to `end` passes through.
+    pub fn new(start: HexCoordFraction<F>, end: HexCoordFraction<F>) -> HexSegmentIterator<F> {
+        HexSegmentIterator {
+            start,
+            direction: end - start,
+            touching_corners: false,
+            corner: None,
+            next: Some(HexSegmentStep {
+                hex: start.round(),
+                t_enter: F::zero(),
+                t_exit: F::one(),
+                entry_edge: None,
+            }),
+        }
+    }
+
+    // options
+    /// Sets whether a hex the segment only touches at a vertex is visited, as a step that enters and leaves it at the same parameter, between the hexes before and after the vertex.
+    /// With this set, every hex the segment touches is visited.
+    pub fn touching_corners(mut self, touching_corners: bool) -> Self {
+        self.touching_corners = touching_corners;
+        self
+    }
+
+    // cube-coordinate dot product
+    fn dot(a: HexCoordFraction<F>, b: HexCoordFraction<F>) -> F {
+        a.q() * b.q() + a.r() * b.r() + a.s() * b.s()
+    }
+
+    // the segment parameter and direction index where the segment leaves `hex`, if before the segment's end,
+    // and the direction index of the neighbor it only touches if it leaves through a vertex
+    fn exit(&self, hex: HexCoord) -> Option<(F, i32, Option<i32>)> {
+        let offset = self.start - hex.into();
+        let tolerance = F::epsilon() * F::from_f64(16.0);
+
+        // the boundary between hex and its neighbor in unit direction d is where (p - hex) · d = 1
+        let mut exit: Option<(F, i32, F, Option<i32>)> = None;
+        for i in 0..6 {
+            let unit: HexCoordFraction<F> = HexCoord::get_unit_coord(i).into();
+            let speed = Self::dot(self.direction, unit);
+            if speed <= F::zero() {
+                continue;
+            }
+
+            let t = (F::one() - Self::dot(offset, unit)) / speed;
+            exit = match exit {
+                // at a vertex, continue into the neighbor the segment moves toward fastest
+                Some((best_t, best_i, best_speed, _)) if (t - best_t).abs() <= tolerance => {
+                    if speed > best_speed { Some((t, i, speed, Some(best_i))) } else { Some((best_t, best_i, best_speed, Some(i))) }
+                },
+                Some((best_t, _, _, _)) if t < best_t => Some((t, i, speed, None)),
+                None => Some((t, i, speed, None)),
+                best => best,
+            };
+        }
+
+        exit.filter(|(t, _, _, _)| *t < F::one()).map(|(t, i, _, touched)| (t, i, touched))
+    }
+}
+
+impl<F: HexFloat> Iterator for HexSegmentIterator<F> {
+    type Item = HexSegmentStep<F>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(corner) = self.corner.take() {
+            return Some(corner);
+        }
+
+        let mut step = self.next.take()?;
+
+        if let Some((t, i, touched)) = self.exit(step.hex) {
+            // guard against stepping backward through rounding error
+            let t = t.max(step.t_enter);
+            step.t_exit = t;
+
+            let hex = step.hex.get_neighbor(i);
+            // half-edge d + 2 of a hex faces its neighbor d + 3, the hex we came from in direction d
+            let mut entry_edge = hex.get_half_edge(i + 2);
+
+            if let Some(j) = touched.filter(|_| self.touching_corners) {
+                let corner = step.hex.get_neighbor(j);
+                self.corner = Some(HexSegmentStep {
+                    hex: corner,
+                    t_enter: t,
+                    t_exit: t,
+                    entry_edge: Some(corner.get_half_edge(j + 2)),
+                });
+
+                // the touched hex and the next one are both neighbors of the vertex, one sixth-turn apart
+                let direction = if HexDirection::new(i) == HexDirection::new(j) + 1 { i + 1 } else { i - 1 };
+                entry_edge = hex.get_half_edge(direction + 2);
+            }
+
+            self.next = Some(HexSegmentStep {
+                hex,
+                t_enter: t,
+                t_exit: F::one(),
+                entry_edge: Some(entry_edge),
+            });
+        }
+
+        Some(step)
+    }
+}
+
+impl<F: HexFloat, V: HexVector3<F>> HexField<F, V> {
+    /// Returns an iterator over the hexes the worldspace segment from `start` to `end` passes through, after projecting it onto the plane of the hex grid.
+    /// Each step includes the segment parameters where it enters and leaves the hex, and the edge it crossed to enter it.
+    pub fn traverse_segment(&self, start: V, end: V) -> HexSegmentIterator<F> {
+        HexSegmentIterator::new(
+            self.hex_coord_fraction(Vector3::from_hex_vector(start)),
+            self.hex_coord_fraction(Vector3::from_hex_vector(end)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn assert_consistent(steps: &[HexSegmentStep]) {
+        assert_eq!(steps[0].t_enter, 0.0);
+        assert_eq!(steps[0].entry_edge, None);
+        assert_eq!(steps.last().unwrap().t_exit, 1.0);
+
+        for pair in steps.windows(2) {
+            let (previous, current) = (pair[0], pair[1]);
+            assert_eq!(previous.t_exit, current.t_enter);
+            assert!(current.t_enter <= current.t_exit);
+
+            let edge = current.entry_edge.unwrap();
+            assert_eq!(edge.hex(), current.hex);
+            assert_eq!(edge.twin().hex(), previous.hex);
+        }
+    }
+
+    #[test]
+    fn segment_crosses_edges() {
+        let field: HexField<f32, [f32; 3]> = HexField::with_layout(
+            [0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [0.0, 1.0, 0.0],
+            HexOrientation::PointyTop,
+            Handedness::RightHanded,
+            1.0,
+        );
+
+        let start = field.get_position(HexCoord::new(-2, 1));
+        let end = field.get_position(HexCoord::new(3, -1));
+        let steps: Vec<HexSegmentStep> = field.traverse_segment(start, end).collect();
+        assert_consistent(&steps);
+        assert_eq!(steps[0].hex, HexCoord::new(-2, 1));
+        assert_eq!(steps.last().unwrap().hex, HexCoord::new(3, -1));
+
+        // each step is the hex containing the middle of its span
+        for step in &steps {
+            let t = (step.t_enter + step.t_exit) / 2.0;
+            let point = [0, 1, 2].map(|i| start[i] + (end[i] - start[i]) * t);
+            assert_eq!(field.get_hex_coord(point), step.hex);
+        }
+
+        let single: Vec<HexSegmentStep> = field.traverse_segment(start, start).collect();
+        assert_eq!(single.len(), 1);
+        assert_consistent(&single);
+    }
+
+    #[test]
+    fn segment_through_vertices() {
+        // the segment leaves the origin through a vertex, runs along an edge, and enters (2, -1, -1) through another vertex
+        let start: HexCoordFraction = HexCoord::ZERO.into();
+        let end: HexCoordFraction = HexCoord::new(2, -1).into();
+        let steps: Vec<HexSegmentStep> = HexSegmentIterator::new(start, end).collect();
+        assert_consistent(&steps);
+        assert_eq!(steps.last().unwrap().hex, HexCoord::new(2, -1));
+
+        // with touching corners, the hex on the other side of the edge is visited where the segment reaches it
+        let touching: Vec<HexSegmentStep> = HexSegmentIterator::new(start, end).touching_corners(true).collect();
+        assert_consistent(&touching);
+        let hexes: Vec<HexCoord> = touching.iter().map(|step| step.hex).collect();
+        assert_eq!(hexes, [HexCoord::ZERO, HexCoord::new(1, 0), HexCoord::new(1, -1), HexCoord::new(2, -1)]);
+        assert_eq!(touching[1].t_enter, touching[1].t_exit);
+
+        // a segment crossing a vertex at an angle touches the third hex there only with the option
+        let vertex: HexCoordFraction = HexCoord::ZERO.get_vertex(0).into();
+        let start = HexCoordFraction::new(0.2, -0.3);
+        let end = vertex * 2.0 - start;
+        let skipping: Vec<HexSegmentStep> = HexSegmentIterator::new(start, end).collect();
+        let touching: Vec<HexSegmentStep> = HexSegmentIterator::new(start, end).touching_corners(true).collect();
+        assert_consistent(&skipping);
+        assert_consistent(&touching);
+        assert_eq!(touching.len(), skipping.len() + 1);
+        let corner = touching.iter().find(|step| step.t_enter == step.t_exit).unwrap();
+        assert!(!skipping.iter().any(|step| step.hex == corner.hex));
+        assert!(HexCoord::ZERO.get_vertex(0).hexes().contains(&corner.hex));
+
+        // a segment exactly along an edge stays on one side of it without zero-length steps
+        let a: HexCoordFraction = HexCoord::ZERO.get_vertex(0).into();
+        let b: HexCoordFraction = HexCoord::ZERO.get_vertex(1).into();
+        let steps: Vec<HexSegmentStep> = HexSegmentIterator::new(a * 2.0 - b, b * 2.0 - a).collect();
+        assert_consistent(&steps);
+        assert!(steps.iter().all(|step| step.t_exit > step.t_enter));
+    }
+}
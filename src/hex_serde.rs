@@ -0,0 +1,79 @@
+use crate::*;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+// `HexCoord` is a string like "1,-2" in human-readable formats so it can be a JSON map key, and a (q, r) tuple otherwise
+impl Serialize for HexCoord {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&format_args!("{},{}", self.q(), self.r()))
+        } else {
+            (self.q(), self.r()).serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HexCoord {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let text = String::deserialize(deserializer)?;
+            let parse = |part: Option<&str>| part.and_then(|part| part.trim().parse::<i32>().ok());
+
+            let mut parts = text.split(',');
+            match (parse(parts.next()), parse(parts.next()), parts.next()) {
+                (Some(q), Some(r), None) => Ok(HexCoord::new(q, r)),
+                _ => Err(de::Error::invalid_value(de::Unexpected::Str(&text), &"a hex coordinate like \"1,-2\"")),
+            }
+        } else {
+            let (q, r) = <(i32, i32)>::deserialize(deserializer)?;
+            Ok(HexCoord::new(q, r))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use serde::{de::DeserializeOwned, Serialize};
+    use std::fmt::Debug;
+
+    fn round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: &T) -> String {
+        let json = serde_json::to_string(value).unwrap();
+        assert_eq!(&serde_json::from_str::<T>(&json).unwrap(), value);
+        json
+    }
+
+    #[test]
+    fn coordinates_round_trip() {
+        assert_eq!(round_trip(&HexCoord::new(1, -2)), "\"1,-2\"");
+        assert_eq!(serde_json::from_str::<HexCoord>("\" 3, 4 \"").unwrap(), HexCoord::new(3, 4));
+        assert!(serde_json::from_str::<HexCoord>("\"1,2,3\"").is_err());
+        assert!(serde_json::from_str::<HexCoord>("\"one,two\"").is_err());
+
+        let fraction: HexCoordFraction<f64> = HexCoordFraction::new(0.5, -1.25);
+        let json = serde_json::to_string(&fraction).unwrap();
+        let parsed: HexCoordFraction<f64> = serde_json::from_str(&json).unwrap();
+        assert_eq!((parsed.q(), parsed.r()), (0.5, -1.25));
+
+        round_trip(&HexCoord::new(2, 1).get_vertex(3));
+        round_trip(&HexCoord::new(-4, 0).get_half_edge(5));
+        round_trip(&HexTransform::new(HexCoord::new(5, -3), 2));
+    }
+
+    #[test]
+    fn collections_round_trip() {
+        let shape = HexShape::new_hexagon(1);
+        assert!(round_trip(&shape).starts_with("[\""));
+
+        let mut data: HashMapHexData<String> = HashMapHexData::new();
+        data.insert(HexCoord::new(0, 0), "grass".to_string());
+        data.insert(HexCoord::new(-1, 2), "water".to_string());
+
+        let json = serde_json::to_string(&data).unwrap();
+        let parsed: HashMapHexData<String> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.get(HexCoord::new(-1, 2)), Some(&"water".to_string()));
+        assert_eq!(parsed.iter().count(), 2);
+
+        let keyed: HashMapHexData<u8> = serde_json::from_str("{\"1,1\": 7}").unwrap();
+        assert_eq!(keyed.get(HexCoord::new(1, 1)), Some(&7));
+    }
+}
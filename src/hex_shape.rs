@@ -1,7 +1,14 @@
 use crate::*;
 use std::vec::Vec;
+use std::collections::{HashSet, VecDeque};
+use std::hash::{Hash, Hasher};
 // use std::ops;
 
+/// A set of hexes.
+/// Shapes compare and hash as sets, regardless of the order their hexes were added in or any duplicates.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct HexShape {
     hexes: Vec<HexCoord>,
 }
@@ -17,6 +24,11 @@ impl HexShape {
         }
     }
 
+    /// Creates a hexagon-shaped `HexShape` containing every hex within `radius` of the origin.
+    pub fn new_hexagon(radius: u32) -> HexShape {
+        Self::new_from_vec(HexRangeIterator::new(radius, HexTransform::IDENTITY).collect())
+    }
+
     // todo: ensure no duplicates
     pub fn push(&mut self, coord: HexCoord) {
         self.hexes.push(coord);
@@ -35,6 +47,10 @@ impl HexShape {
         self.hexes.len() as u32
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.hexes.is_empty()
+    }
+
     pub fn transformed<'a>(&'a self, transform: HexTransform) -> HexShapeView<'a> {
         HexShapeView::new(
             self,
@@ -42,14 +58,14 @@ impl HexShape {
         )
     }
 
-    pub fn translated(&self, translation: HexCoord) -> HexShapeView {
+    pub fn translated(&self, translation: HexCoord) -> HexShapeView<'_> {
         HexShapeView::new(
             self,
             HexTransform::from_translation(translation),
         )
     }
 
-    pub fn rotated(&self, rotation: i32) -> HexShapeView {
+    pub fn rotated(&self, rotation: i32) -> HexShapeView<'_> {
         HexShapeView::new(
             self,
             HexTransform::from_rotation(rotation),
@@ -60,6 +76,300 @@ impl HexShape {
         self.hexes.get(index as usize).copied()
     }
 
+    // connectivity
+    /// Returns the hexes of `self` reachable from `start` by stepping between neighboring hexes of `self`.
+    /// The result is empty if `start` is not in `self`.
+    pub fn flood_fill_from(&self, start: HexCoord) -> HexShape {
+        let hexes = self.hex_set();
+        if !hexes.contains(&start) {
+            return HexShape::new();
+        }
+
+        Self::sorted_shape(Self::flood(vec![start], |hex| hexes.contains(&hex)))
+    }
+
+    /// Partitions `self` into its connected components, where hexes are connected if they are neighbors.
+    /// Each component is sorted, and components are ordered by their smallest hex.
+    pub fn connected_components(&self) -> Vec<HexShape> {
+        let hexes = self.hex_set();
+        Self::partition(&hexes)
+    }
+
+    /// Returns whether every hex of `self` can be reached from every other by stepping between neighboring hexes of `self`.
+    /// An empty shape is considered connected.
+    pub fn is_connected(&self) -> bool {
+        match self.hexes.first() {
+            Some(first) => self.flood_fill_from(*first).len() as usize == self.hex_set().len(),
+            None => true,
+        }
+    }
+
+    /// Returns the enclosed empty regions of `self`: connected groups of hexes not in `self` that cannot reach infinity without crossing `self`.
+    /// Each hole is sorted, and holes are ordered by their smallest hex.
+    pub fn holes(&self) -> Vec<HexShape> {
+        let hexes = self.hex_set();
+        if hexes.is_empty() {
+            return Vec::new();
+        }
+
+        // bound the shape by a hexagon-like region one hex larger than the shape in each cubical coordinate
+        let q_min = hexes.iter().map(|hex| hex.q()).min().unwrap() - 1;
+        let q_max = hexes.iter().map(|hex| hex.q()).max().unwrap() + 1;
+        let r_min = hexes.iter().map(|hex| hex.r()).min().unwrap() - 1;
+        let r_max = hexes.iter().map(|hex| hex.r()).max().unwrap() + 1;
+        let s_min = hexes.iter().map(|hex| hex.s()).min().unwrap() - 1;
+        let s_max = hexes.iter().map(|hex| hex.s()).max().unwrap() + 1;
+
+        let in_bounds = |hex: HexCoord| {
+            (q_min..=q_max).contains(&hex.q()) &&
+            (r_min..=r_max).contains(&hex.r()) &&
+            (s_min..=s_max).contains(&hex.s())
+        };
+
+        let mut empty = HashSet::new();
+        let mut border = Vec::new();
+        for q in q_min..=q_max {
+            for r in r_min..=r_max {
+                let hex = HexCoord::new(q, r);
+                if !in_bounds(hex) || hexes.contains(&hex) {
+                    continue;
+                }
+
+                empty.insert(hex);
+                if hex.q() == q_min || hex.q() == q_max ||
+                    hex.r() == r_min || hex.r() == r_max ||
+                    hex.s() == s_min || hex.s() == s_max {
+                    border.push(hex);
+                }
+            }
+        }
+
+        // the border layer contains no hexes of the shape, so everything reachable from it is outside
+        let outside = Self::flood(border, |hex| empty.contains(&hex));
+        let enclosed: HashSet<HexCoord> = empty.difference(&outside).copied().collect();
+
+        Self::partition(&enclosed)
+    }
+
+    // symmetry
+    /// Returns a copy of `self` reflected across the line through the origin and hex *(2, -1, -1)*.
+    pub fn reflected(&self) -> HexShape {
+        HexShape::new_from_vec(self.hexes.iter().map(|hex| hex.reflect()).collect())
+    }
+
+    /// Returns a normalized representative of the shapes equal to `self` up to rotation and translation, along with the transform that maps `self` onto it.
+    /// Two shapes are congruent under rotation and translation exactly when their canonical shapes are equal.
+    /// The canonical shape is sorted, free of duplicates, and has its smallest hex at the origin.
+    pub fn canonical(&self) -> (HexShape, HexTransform) {
+        self.orientations()
+            .into_iter()
+            .min_by(|a, b| a.0.hexes.cmp(&b.0.hexes))
+            .unwrap()
+    }
+
+    /// Like `canonical`, but also considers reflections of `self`.
+    /// If the returned flag is `true`, the returned transform maps `self.reflected()`, rather than `self`, onto the canonical shape.
+    pub fn canonical_with_reflection(&self) -> (HexShape, HexTransform, bool) {
+        let (shape, transform) = self.canonical();
+        let (reflected_shape, reflected_transform) = self.reflected().canonical();
+
+        if reflected_shape.hexes < shape.hexes {
+            (reflected_shape, reflected_transform, true)
+        } else {
+            (shape, transform, false)
+        }
+    }
+
+    /// Returns whether `self` and `other` contain the same hexes up to rotation and translation, and optionally reflection.
+    pub fn is_congruent(&self, other: &HexShape, allow_reflection: bool) -> bool {
+        if allow_reflection {
+            self.canonical_with_reflection().0 == other.canonical_with_reflection().0
+        } else {
+            self.canonical().0 == other.canonical().0
+        }
+    }
+
+    /// Returns every transform that maps `self` onto itself, including the identity.
+    /// Because `HexTransform` cannot represent reflections, only rotational symmetries are reported.
+    pub fn symmetry_group(&self) -> Vec<HexTransform> {
+        let (normalized, translation) = Self::normalized(self.hexes.clone());
+        let offset = -translation;
+
+        self.orientations()
+            .into_iter()
+            .filter(|(shape, _)| *shape == normalized)
+            .map(|(_, transform)| transform.translated(offset))
+            .collect()
+    }
+
+    /// Returns each distinct rotation of `self`, normalized as in `canonical`, along with the transform that maps `self` onto it.
+    /// Rotations that produce the same shape up to translation are reported once, using the smallest rotation.
+    pub fn distinct_orientations(&self) -> Vec<(HexShape, HexTransform)> {
+        let mut result: Vec<(HexShape, HexTransform)> = Vec::new();
+        for (shape, transform) in self.orientations() {
+            if !result.iter().any(|(existing, _)| *existing == shape) {
+                result.push((shape, transform));
+            }
+        }
+
+        result
+    }
+
+    // the six rotations of self, each normalized, with the transforms producing them
+    fn orientations(&self) -> Vec<(HexShape, HexTransform)> {
+        (0..6)
+            .map(|rotation| {
+                let rotated = self.hexes.iter().map(|hex| hex.rotate_around(HexCoord::ZERO, rotation)).collect();
+                let (shape, translation) = Self::normalized(rotated);
+                (shape, HexTransform::new(translation, rotation))
+            })
+            .collect()
+    }
+
+    // sorts and deduplicates hexes, then translates them so the smallest is at the origin
+    pub(crate) fn normalized(mut hexes: Vec<HexCoord>) -> (HexShape, HexCoord) {
+        hexes.sort();
+        hexes.dedup();
+
+        let translation = match hexes.first() {
+            Some(first) => -*first,
+            None => HexCoord::ZERO,
+        };
+
+        for hex in &mut hexes {
+            *hex += translation;
+        }
+
+        (HexShape::new_from_vec(hexes), translation)
+    }
+
+    // morphology
+    /// Returns the Minkowski sum of `self` and `other`: every hex `a + b` for `a` in `self` and `b` in `other`.
+    pub fn dilate(&self, other: &HexShape) -> HexShape {
+        let mut result = HashSet::new();
+        for a in &self.hexes {
+            for b in &other.hexes {
+                result.insert(*a + *b);
+            }
+        }
+
+        Self::sorted_shape(result)
+    }
+
+    /// Returns every hex within `radius` of a hex of `self`.
+    pub fn dilate_by(&self, radius: u32) -> HexShape {
+        self.dilate(&HexShape::new_hexagon(radius))
+    }
+
+    /// Returns every hex `h` for which `other` translated by `h` lies entirely within `self`.
+    /// Eroding by an empty shape returns an empty shape, although mathematically the result is unbounded.
+    pub fn erode(&self, other: &HexShape) -> HexShape {
+        let hexes = self.hex_set();
+        let anchor = match other.hexes.first() {
+            Some(anchor) => *anchor,
+            None => return HexShape::new(),
+        };
+
+        // any valid translation must carry the anchor onto some hex of the shape
+        let result = hexes.iter()
+            .map(|hex| *hex - anchor)
+            .filter(|translation| other.hexes.iter().all(|b| hexes.contains(&(*b + *translation))))
+            .collect();
+
+        Self::sorted_shape(result)
+    }
+
+    /// Returns every hex of `self` whose distance to the nearest hex outside `self` is greater than `radius`.
+    pub fn erode_by(&self, radius: u32) -> HexShape {
+        self.erode(&HexShape::new_hexagon(radius))
+    }
+
+    /// Returns the morphological opening of `self` by `other`, the erosion followed by the dilation.
+    /// Opening removes parts of `self` too narrow to contain `other`.
+    pub fn open(&self, other: &HexShape) -> HexShape {
+        self.erode(other).dilate(other)
+    }
+
+    /// Returns the morphological closing of `self` by `other`, the dilation followed by the erosion.
+    /// Closing fills gaps and notches in `self` too narrow to contain `other`.
+    pub fn close(&self, other: &HexShape) -> HexShape {
+        self.dilate(other).erode(other)
+    }
+
+    /// Returns the hexes not in `self` that neighbor a hex of `self`.
+    pub fn outer_ring(&self) -> HexShape {
+        let hexes = self.hex_set();
+        let result = hexes.iter()
+            .flat_map(|hex| hex.neighbors())
+            .filter(|neighbor| !hexes.contains(neighbor))
+            .collect();
+
+        Self::sorted_shape(result)
+    }
+
+    /// Returns the hexes of `self` that neighbor a hex not in `self`.
+    pub fn inner_ring(&self) -> HexShape {
+        let hexes = self.hex_set();
+        let result = hexes.iter()
+            .filter(|hex| hex.neighbors().any(|neighbor| !hexes.contains(&neighbor)))
+            .copied()
+            .collect();
+
+        Self::sorted_shape(result)
+    }
+
+    fn hex_set(&self) -> HashSet<HexCoord> {
+        self.hexes.iter().copied().collect()
+    }
+
+    fn flood<F: Fn(HexCoord) -> bool>(starts: Vec<HexCoord>, passable: F) -> HashSet<HexCoord> {
+        let mut visited: HashSet<HexCoord> = starts.iter().copied().collect();
+        let mut queue: VecDeque<HexCoord> = starts.into_iter().collect();
+
+        while let Some(hex) = queue.pop_front() {
+            for neighbor in hex.neighbors() {
+                if passable(neighbor) && visited.insert(neighbor) {
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+
+        visited
+    }
+
+    fn partition(hexes: &HashSet<HexCoord>) -> Vec<HexShape> {
+        let mut remaining: Vec<HexCoord> = hexes.iter().copied().collect();
+        remaining.sort();
+
+        let mut assigned = HashSet::new();
+        let mut components = Vec::new();
+        for hex in remaining {
+            if assigned.contains(&hex) {
+                continue;
+            }
+
+            let component = Self::flood(vec![hex], |neighbor| hexes.contains(&neighbor));
+            assigned.extend(component.iter().copied());
+            components.push(Self::sorted_shape(component));
+        }
+
+        components
+    }
+
+    // the hexes of `self` sorted and without duplicates, so shapes with the same hexes compare equal
+    fn sorted_hexes(&self) -> Vec<HexCoord> {
+        let mut hexes = self.hexes.clone();
+        hexes.sort();
+        hexes.dedup();
+        hexes
+    }
+
+    fn sorted_shape(hexes: HashSet<HexCoord>) -> HexShape {
+        let mut hexes: Vec<HexCoord> = hexes.into_iter().collect();
+        hexes.sort();
+        HexShape::new_from_vec(hexes)
+    }
+
     // pub fn get<I>(&self, index: I) -> Option<&<I as SliceIndex<[HexCoord]>>::Output> where I: SliceIndex<[HexCoord]> {
     //     self.hexes.get(index)
     // }
@@ -73,6 +383,25 @@ impl HexShape {
 //     }
 // }
 
+impl PartialEq for HexShape {
+    fn eq(&self, other: &HexShape) -> bool {
+        self.sorted_hexes() == other.sorted_hexes()
+    }
+}
+
+impl Eq for HexShape {}
+
+impl Hash for HexShape {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.sorted_hexes().hash(state);
+    }
+}
+
+impl Default for HexShape {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl<'a> IntoIterator for &'a HexShape {
     type Item = &'a HexCoord;
@@ -106,30 +435,30 @@ impl<'a> HexShapeView<'a> {
         self.shape.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.shape.is_empty()
+    }
+
     // returns a copy because we can't return a transformed slice or reference
     pub fn get(&self, index: u32) -> Option<HexCoord> {
-        match self.shape.get(index) {
-            Some(coord) => Some(self.transform * coord),
-            None => None,
-
-        }
+        self.shape.get(index).map(|coord| self.transform * coord)
     }
 
-    pub fn transformed(&self, transform: HexTransform) -> HexShapeView {
+    pub fn transformed(&self, transform: HexTransform) -> HexShapeView<'_> {
         HexShapeView::new(
             self.shape,
             transform * self.transform,
         )
     }
 
-    pub fn translated(&self, translation: HexCoord) -> HexShapeView {
+    pub fn translated(&self, translation: HexCoord) -> HexShapeView<'_> {
         HexShapeView::new(
             self.shape,
             HexTransform::from_translation(translation) * self.transform,
         )
     }
 
-    pub fn rotated(&self, rotation: i32) -> HexShapeView {
+    pub fn rotated(&self, rotation: i32) -> HexShapeView<'_> {
         HexShapeView::new(
             self.shape,
             HexTransform::from_rotation(rotation) * self.transform,
@@ -148,3 +477,137 @@ impl<'a, 'b> IntoIterator for &'b HexShapeView<'a> {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use crate::*;
+    use std::collections::HashSet;
+
+    fn ring(center: HexCoord) -> HexShape {
+        HexShape::new_from_vec(center.neighbors().collect())
+    }
+
+    #[test]
+    fn ring_is_connected_with_one_hole() {
+        let shape = ring(HexCoord::new(2, -1));
+        assert!(shape.is_connected());
+        assert_eq!(shape.connected_components().len(), 1);
+
+        let holes = shape.holes();
+        assert_eq!(holes.len(), 1);
+        assert_eq!(holes[0].len(), 1);
+        assert_eq!(holes[0].get(0), Some(HexCoord::new(2, -1)));
+    }
+
+    #[test]
+    fn separate_islands_are_separate_components() {
+        let mut shape = ring(HexCoord::ZERO);
+        shape.push(HexCoord::new(5, 0));
+        shape.push(HexCoord::new(5, 1));
+
+        assert!(!shape.is_connected());
+        let components = shape.connected_components();
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].len(), 6);
+        assert_eq!(components[1].len(), 2);
+
+        assert_eq!(shape.flood_fill_from(HexCoord::new(5, 1)).len(), 2);
+        assert!(shape.flood_fill_from(HexCoord::ZERO).is_empty());
+    }
+
+    #[test]
+    fn open_shape_has_no_holes() {
+        let mut shape = ring(HexCoord::ZERO);
+        let gap = HexCoord::ZERO.get_neighbor(0);
+        shape = HexShape::new_from_vec(shape.into_iter().copied().filter(|hex| *hex != gap).collect());
+
+        assert!(shape.is_connected());
+        assert!(shape.holes().is_empty());
+    }
+
+    #[test]
+    fn dilate_and_erode_hexagons() {
+        let hexagon = HexShape::new_hexagon(2);
+        assert_eq!(hexagon.len(), 19);
+        assert_eq!(hexagon.dilate_by(1).len(), 37);
+        assert_eq!(hexagon.erode_by(1).len(), 7);
+        assert_eq!(hexagon.erode_by(2).len(), 1);
+        assert!(hexagon.erode_by(3).is_empty());
+    }
+
+    #[test]
+    fn close_fills_small_hole() {
+        let shape = ring(HexCoord::ZERO);
+        let closed = shape.close(&HexShape::new_hexagon(1));
+        assert_eq!(closed.len(), 7);
+        assert!(closed.contains(HexCoord::ZERO));
+        assert!(closed.holes().is_empty());
+
+        assert!(shape.open(&HexShape::new_hexagon(1)).is_empty());
+    }
+
+    #[test]
+    fn rings_of_hexagon() {
+        let hexagon = HexShape::new_hexagon(1);
+        assert_eq!(hexagon.outer_ring().len(), 12);
+        assert_eq!(hexagon.inner_ring().len(), 6);
+        assert!(!hexagon.inner_ring().contains(HexCoord::ZERO));
+    }
+
+    fn shape(coords: &[(i32, i32)]) -> HexShape {
+        HexShape::new_from_vec(coords.iter().map(|(q, r)| HexCoord::new(*q, *r)).collect())
+    }
+
+    #[test]
+    fn canonical_ignores_rotation_and_translation() {
+        let bent = shape(&[(0, 0), (1, 0), (1, 1)]);
+        let moved = HexShape::new_from_vec(bent.into_iter().map(|hex| HexTransform::new(HexCoord::new(4, -7), 2) * *hex).collect());
+
+        let (canonical, transform) = moved.canonical();
+        assert_eq!(canonical, bent.canonical().0);
+        for hex in &moved {
+            assert!(canonical.contains(transform * *hex));
+        }
+        assert!(bent.is_congruent(&moved, false));
+    }
+
+    #[test]
+    fn equality_ignores_order_and_duplicates() {
+        let bent = shape(&[(0, 0), (1, 0), (1, 1)]);
+        let shuffled = shape(&[(1, 1), (0, 0), (1, 0), (0, 0)]);
+        assert_eq!(bent, shuffled);
+        assert_ne!(bent, shape(&[(0, 0), (1, 0)]));
+        assert_eq!([bent, shuffled].into_iter().collect::<HashSet<HexShape>>().len(), 1);
+    }
+
+    #[test]
+    fn chiral_shape_needs_reflection() {
+        let chiral = shape(&[(0, 0), (1, 0), (2, 0), (2, 1)]);
+        let mirrored = chiral.reflected();
+
+        assert!(!chiral.is_congruent(&mirrored, false));
+        assert!(chiral.is_congruent(&mirrored, true));
+
+        let (canonical, transform, reflected) = mirrored.canonical_with_reflection();
+        let source = if reflected { mirrored.reflected() } else { mirrored.clone() };
+        for hex in &source {
+            assert!(canonical.contains(transform * *hex));
+        }
+    }
+
+    #[test]
+    fn symmetry_groups() {
+        let hexagon = HexShape::new_from_vec(HexRangeIterator::new(1, HexTransform::from_translation(HexCoord::new(2, 3))).collect());
+        let symmetries = hexagon.symmetry_group();
+        assert_eq!(symmetries.len(), 6);
+        for transform in symmetries {
+            for hex in &hexagon {
+                assert!(hexagon.contains(transform * *hex));
+            }
+        }
+
+        let line = shape(&[(0, 0), (1, 0), (2, 0)]);
+        assert_eq!(line.symmetry_group().len(), 2);
+        assert_eq!(line.distinct_orientations().len(), 3);
+        assert_eq!(shape(&[(0, 0), (1, 0), (2, 0), (2, 1)]).distinct_orientations().len(), 6);
+    }
+}
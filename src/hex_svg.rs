@@ -0,0 +1,230 @@
+use crate::*;
+use crate::hex_vector::HexVector2;
+use std::fmt::Write;
+
+/// The fill and stroke of a hex, edge, or vertex marker drawn by `HexSvg`.
+/// Colors are any SVG paint value, such as `"#ff8800"` or `"none"`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct HexSvgStyle {
+    pub fill: String,
+    pub stroke: String,
+    pub stroke_width: f64,
+}
+
+impl HexSvgStyle {
+    /// Creates a style with the given fill, stroke, and stroke width.
+    pub fn new(fill: &str, stroke: &str, stroke_width: f64) -> HexSvgStyle {
+        HexSvgStyle {
+            fill: fill.to_string(),
+            stroke: stroke.to_string(),
+            stroke_width,
+        }
+    }
+}
+
+impl Default for HexSvgStyle {
+    fn default() -> Self {
+        Self::new("none", "black", 1.0)
+    }
+}
+
+enum HexSvgElement {
+    Hex(HexCoord, HexSvgStyle),
+    Edge(HexHalfEdge, HexSvgStyle),
+    Vertex(HexVertex, f64, HexSvgStyle),
+}
+
+/// Renders hexes, edges, and vertices to an SVG string, laid out by a `HexField2`.
+/// SVG uses y-down coordinates, so use a left-handed `HexField2` to draw the grid as a right-handed `HexField` appears in a y-up plot.
+///
+/// Elements are drawn in the order they are added, and numbers are written with at most three decimal places, so the output is stable enough to compare in tests.
+/// The SVG's view box fits everything drawn.
+pub struct HexSvg<'a, F: HexFloat, V: HexVector2<F>> {
+    field: &'a HexField2<F, V>,
+    coordinate_labels: bool,
+    elements: Vec<HexSvgElement>,
+}
+
+impl<'a, F: HexFloat, V: HexVector2<F>> HexSvg<'a, F, V> {
+    // constructor
+    /// Creates an empty drawing laid out by `field`, without coordinate labels.
+    pub fn new(field: &'a HexField2<F, V>) -> HexSvg<'a, F, V> {
+        HexSvg {
+            field,
+            coordinate_labels: false,
+            elements: Vec::new(),
+        }
+    }
+
+    // options
+    /// Sets whether each hex is labeled with its coordinates.
+    pub fn coordinate_labels(mut self, coordinate_labels: bool) -> Self {
+        self.coordinate_labels = coordinate_labels;
+        self
+    }
+
+    // methods
+    /// Adds a hex polygon.
+    pub fn add_hex(&mut self, hex: HexCoord, style: &HexSvgStyle) {
+        self.elements.push(HexSvgElement::Hex(hex, style.clone()));
+    }
+
+    /// Adds a hex polygon for each hex of `shape`.
+    pub fn add_shape(&mut self, shape: &HexShape, style: &HexSvgStyle) {
+        for hex in shape {
+            self.add_hex(*hex, style);
+        }
+    }
+
+    /// Adds a hex polygon for each pair of a hex and its value in `data`, such as `HashMapHexData::iter`, styled by `style`.
+    /// Hexes are added in sorted order so the output doesn't depend on the order `data` yields them in.
+    /// `data` is a sequence of pairs rather than a `&impl HexData<T>` because `HexData` has no way to list its hexes, and adding one would break its existing implementations.
+    pub fn add_data<'b, T: 'b, I: IntoIterator<Item = (HexCoord, &'b T)>, S: Fn(HexCoord, &T) -> HexSvgStyle>(&mut self, data: I, style: S) {
+        let mut entries: Vec<(HexCoord, &T)> = data.into_iter().collect();
+        entries.sort_by_key(|(hex, _)| *hex);
+
+        for (hex, value) in entries {
+            self.elements.push(HexSvgElement::Hex(hex, style(hex, value)));
+        }
+    }
+
+    /// Adds a line highlighting an edge.
+    pub fn add_edge(&mut self, edge: HexHalfEdge, style: &HexSvgStyle) {
+        self.elements.push(HexSvgElement::Edge(edge, style.clone()));
+    }
+
+    /// Adds a circle with the given radius marking a vertex.
+    pub fn add_vertex(&mut self, vertex: HexVertex, radius: f64, style: &HexSvgStyle) {
+        self.elements.push(HexSvgElement::Vertex(vertex, radius, style.clone()));
+    }
+
+    /// Returns the SVG document.
+    pub fn render(&self) -> String {
+        let mut body = String::new();
+        let mut bounds: Option<([f64; 2], [f64; 2])> = None;
+        let mut include = |[x, y]: [f64; 2], margin: f64| {
+            let (min, max) = bounds.get_or_insert(([x, y], [x, y]));
+            *min = [min[0].min(x - margin), min[1].min(y - margin)];
+            *max = [max[0].max(x + margin), max[1].max(y + margin)];
+        };
+
+        for element in &self.elements {
+            match element {
+                HexSvgElement::Hex(hex, style) => {
+                    let corners: Vec<[f64; 2]> = hex.vertices().map(|vertex| self.point(vertex.into())).collect();
+                    for corner in &corners {
+                        include(*corner, style.stroke_width / 2.0);
+                    }
+
+                    let points: Vec<String> = corners.iter().map(|[x, y]| format!("{},{}", number(*x), number(*y))).collect();
+                    writeln!(body, "  <polygon points=\"{}\"{}/>", points.join(" "), attributes(style)).unwrap();
+
+                    if self.coordinate_labels {
+                        let [x, y] = self.point((*hex).into());
+                        let [width, height] = self.field.size().xy().map(|x| x.as_f64());
+                        writeln!(
+                            body,
+                            "  <text x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\">{}, {}, {}</text>",
+                            number(x), number(y), number(width.min(height) * 0.4), hex.q(), hex.r(), hex.s(),
+                        ).unwrap();
+                    }
+                },
+                HexSvgElement::Edge(edge, style) => {
+                    let [x1, y1] = self.point(edge.source().into());
+                    let [x2, y2] = self.point(edge.destination().into());
+                    include([x1, y1], style.stroke_width / 2.0);
+                    include([x2, y2], style.stroke_width / 2.0);
+
+                    writeln!(
+                        body,
+                        "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"{}/>",
+                        number(x1), number(y1), number(x2), number(y2), attributes(style),
+                    ).unwrap();
+                },
+                HexSvgElement::Vertex(vertex, radius, style) => {
+                    let [x, y] = self.point((*vertex).into());
+                    include([x, y], radius + style.stroke_width / 2.0);
+
+                    writeln!(body, "  <circle cx=\"{}\" cy=\"{}\" r=\"{}\"{}/>", number(x), number(y), number(*radius), attributes(style)).unwrap();
+                },
+            }
+        }
+
+        let ([min_x, min_y], [max_x, max_y]) = bounds.unwrap_or(([0.0, 0.0], [0.0, 0.0]));
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n{}</svg>\n",
+            number(min_x), number(min_y), number(max_x - min_x), number(max_y - min_y), body,
+        )
+    }
+
+    fn point(&self, coord: HexCoordFraction<F>) -> [f64; 2] {
+        let position = self.field.position(coord);
+        [position.x.as_f64(), position.y.as_f64()]
+    }
+}
+
+fn attributes(style: &HexSvgStyle) -> String {
+    format!(
+        " fill=\"{}\" stroke=\"{}\" stroke-width=\"{}\"",
+        escape(&style.fill), escape(&style.stroke), number(style.stroke_width),
+    )
+}
+
+// formats with at most three decimal places and no negative zero, so output is stable across platforms
+fn number(x: f64) -> String {
+    let formatted = format!("{:.3}", x);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" { "0".to_string() } else { trimmed.to_string() }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn layout() -> HexField2<f32, [f32; 2]> {
+        HexField2::new([0.0, 0.0], [10.0, 10.0], HexOrientation::FlatTop, Handedness::LeftHanded)
+    }
+
+    #[test]
+    fn single_hex_snapshot() {
+        let field = layout();
+        let mut svg = HexSvg::new(&field).coordinate_labels(true);
+        svg.add_hex(HexCoord::ZERO, &HexSvgStyle::new("#ccc", "black", 2.0));
+        svg.add_edge(HexCoord::ZERO.get_half_edge(0), &HexSvgStyle::new("none", "red", 2.0));
+        svg.add_vertex(HexCoord::ZERO.get_vertex(3), 1.5, &HexSvgStyle::new("blue", "none", 0.0));
+
+        assert_eq!(svg.render(), concat!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"-11.5 -9.66 22.5 19.321\">\n",
+            "  <polygon points=\"10,0 5,-8.66 -5,-8.66 -10,0 -5,8.66 5,8.66\" fill=\"#ccc\" stroke=\"black\" stroke-width=\"2\"/>\n",
+            "  <text x=\"0\" y=\"0\" font-size=\"4\" text-anchor=\"middle\" dominant-baseline=\"central\">0, 0, 0</text>\n",
+            "  <line x1=\"10\" y1=\"0\" x2=\"5\" y2=\"-8.66\" fill=\"none\" stroke=\"red\" stroke-width=\"2\"/>\n",
+            "  <circle cx=\"-10\" cy=\"0\" r=\"1.5\" fill=\"blue\" stroke=\"none\" stroke-width=\"0\"/>\n",
+            "</svg>\n",
+        ));
+    }
+
+    #[test]
+    fn data_is_sorted_and_escaped() {
+        let field = layout();
+        let mut data: HashMapHexData<u8> = HashMapHexData::new();
+        for (i, hex) in HexRangeIterator::new(1, HexTransform::IDENTITY).enumerate() {
+            data.insert(hex, i as u8);
+        }
+
+        let render = || {
+            let mut svg = HexSvg::new(&field);
+            svg.add_data(data.iter(), |_, value| HexSvgStyle::new(if *value == 0 { "<bad>" } else { "white" }, "black", 1.0));
+            svg.render()
+        };
+        let rendered = render();
+        assert_eq!(rendered, render());
+        assert_eq!(rendered.matches("<polygon").count(), 7);
+        assert!(rendered.contains("fill=\"&lt;bad&gt;\""));
+
+        assert_eq!(HexSvg::new(&field).render(), "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 0 0\">\n</svg>\n");
+    }
+}
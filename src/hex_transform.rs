@@ -1,10 +1,13 @@
 use crate::*;
-use std::ops;
+use std::{fmt, ops, str::FromStr};
 
+/// A rotation around the origin followed by a translation.
+/// Rotations are given as the direction `HexDirection::Zero` turns to, or as an `i32` number of sixth-turns CCW.
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HexTransform {
     translation: HexCoord,
-    rotation: i32,
+    rotation: HexDirection,
 }
 
 impl HexTransform {
@@ -13,36 +16,42 @@ impl HexTransform {
         self.translation
     }
 
+    /// Returns the number of sixth-turns CCW that `self` rotates by, from 0 to 5.
     pub fn rotation(&self) -> i32 {
+        self.rotation.index()
+    }
+
+    /// Returns the direction `HexDirection::Zero` turns to under `self`.
+    pub fn rotation_direction(&self) -> HexDirection {
         self.rotation
     }
 
     // constructor
-    pub fn new(translation: HexCoord, rotation: i32) -> HexTransform {
+    pub fn new<R: Into<HexDirection>>(translation: HexCoord, rotation: R) -> HexTransform {
         HexTransform {
             translation,
-            rotation,
+            rotation: rotation.into(),
         }
     }
 
     pub fn from_translation(translation: HexCoord) -> HexTransform {
         HexTransform {
             translation,
-            rotation: 0,
+            rotation: HexDirection::Zero,
         }
     }
 
-    pub fn from_rotation(rotation: i32) -> HexTransform {
+    pub fn from_rotation<R: Into<HexDirection>>(rotation: R) -> HexTransform {
         HexTransform {
             translation: HexCoord::ZERO,
-            rotation,
+            rotation: rotation.into(),
         }
     }
 
     // constants
     pub const IDENTITY:HexTransform = HexTransform {
         translation: HexCoord::ZERO,
-        rotation: 0,
+        rotation: HexDirection::Zero,
     };
 
     // methods
@@ -51,7 +60,11 @@ impl HexTransform {
     }
 
     pub fn inverse(&self) -> HexTransform {
-        todo!("implement");
+        let rotation = -self.rotation();
+        HexTransform::new(
+            -self.translation().rotate_around(HexCoord::ZERO, rotation),
+            rotation,
+        )
     }
 
     pub fn transformed(&self, transform: HexTransform) -> HexTransform{
@@ -62,7 +75,7 @@ impl HexTransform {
         HexTransform::from_translation(translation) * *self
     }
 
-    pub fn rotated(&self, rotation: i32) -> HexTransform {
+    pub fn rotated<R: Into<HexDirection>>(&self, rotation: R) -> HexTransform {
         HexTransform::from_rotation(rotation) * *self
     }
 }
@@ -73,7 +86,7 @@ impl ops::Mul for HexTransform {
     fn mul(self, other: HexTransform) -> HexTransform {
         HexTransform::new(
             other.translation().rotate_around(HexCoord::ZERO, self.rotation()) + self.translation(),
-            self.rotation() + other.rotation(),
+            self.rotation_direction() + other.rotation(),
         )
     }
 }
@@ -97,4 +110,35 @@ impl ops::MulAssign<HexTransform> for HexCoord {
         *self = other * *self;
     }
 
-}
\ No newline at end of file
+}
+
+/// Writes the transform as its translation and rotation in sixth-turns, such as `(1, -2, 1) rot 3`.
+impl fmt::Display for HexTransform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} rot {}", self.translation(), self.rotation())
+    }
+}
+
+/// Parses a transform in the format written by `Display`; the translation may be in any format `HexCoord` accepts.
+impl FromStr for HexTransform {
+    type Err = HexParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (translation, rotation) = s.rsplit_once("rot").ok_or_else(|| HexParseError::InvalidFormat(s.to_string()))?;
+        let rotation: i32 = rotation.trim().parse().map_err(|_| HexParseError::InvalidNumber(rotation.trim().to_string()))?;
+        Ok(HexTransform::new(translation.parse()?, rotation))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn inverse_undoes_transform() {
+        let transform = HexTransform::new(HexCoord::new(3, -5), 2);
+        let hex = HexCoord::new(-1, 4);
+        assert_eq!(transform.inverse() * (transform * hex), hex);
+        assert_eq!(transform * (transform.inverse() * hex), hex);
+    }
+}
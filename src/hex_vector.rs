@@ -0,0 +1,288 @@
+use crate::*;
+use std::ops;
+
+/// A 3D vector type that `HexField` can accept and return.
+/// Implemented for `[F; 3]`, and for the vector types of cgmath, glam, and mint when the corresponding cargo features are enabled.
+pub trait HexVector3<F: HexFloat>: Copy {
+    /// Creates a vector from its components.
+    fn from_xyz(x: F, y: F, z: F) -> Self;
+
+    /// Returns the components of `self`.
+    fn xyz(&self) -> [F; 3];
+}
+
+/// A 2D vector type that `HexField2` can accept and return.
+/// Implemented for `[F; 2]`, and for the vector types of cgmath, glam, and mint when the corresponding cargo features are enabled.
+pub trait HexVector2<F: HexFloat>: Copy {
+    /// Creates a vector from its components.
+    fn from_xy(x: F, y: F) -> Self;
+
+    /// Returns the components of `self`.
+    fn xy(&self) -> [F; 2];
+}
+
+/// The vector type `HexField` uses when none is specified: `cgmath::Vector3<F>` with the `cgmath` feature, as before other vector libraries were supported.
+#[cfg(feature = "cgmath")]
+pub type DefaultVector3<F> = cgmath::Vector3<F>;
+
+/// The vector type `HexField` uses when none is specified: `[F; 3]`, since the `cgmath` feature is disabled.
+#[cfg(not(feature = "cgmath"))]
+pub type DefaultVector3<F> = [F; 3];
+
+/// The vector type `HexField2` uses when none is specified: `cgmath::Vector2<F>` with the `cgmath` feature.
+#[cfg(feature = "cgmath")]
+pub type DefaultVector2<F> = cgmath::Vector2<F>;
+
+/// The vector type `HexField2` uses when none is specified: `[F; 2]`, since the `cgmath` feature is disabled.
+#[cfg(not(feature = "cgmath"))]
+pub type DefaultVector2<F> = [F; 2];
+
+// arrays
+impl<F: HexFloat> HexVector3<F> for [F; 3] {
+    fn from_xyz(x: F, y: F, z: F) -> Self {
+        [x, y, z]
+    }
+
+    fn xyz(&self) -> [F; 3] {
+        *self
+    }
+}
+
+impl<F: HexFloat> HexVector2<F> for [F; 2] {
+    fn from_xy(x: F, y: F) -> Self {
+        [x, y]
+    }
+
+    fn xy(&self) -> [F; 2] {
+        *self
+    }
+}
+
+// cgmath
+#[cfg(feature = "cgmath")]
+impl<F: HexFloat> HexVector3<F> for cgmath::Vector3<F> {
+    fn from_xyz(x: F, y: F, z: F) -> Self {
+        cgmath::Vector3::new(x, y, z)
+    }
+
+    fn xyz(&self) -> [F; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+#[cfg(feature = "cgmath")]
+impl<F: HexFloat> HexVector2<F> for cgmath::Vector2<F> {
+    fn from_xy(x: F, y: F) -> Self {
+        cgmath::Vector2::new(x, y)
+    }
+
+    fn xy(&self) -> [F; 2] {
+        [self.x, self.y]
+    }
+}
+
+// glam
+#[cfg(feature = "glam")]
+macro_rules! impl_glam {
+    ($scalar:ty, $vector3:ty, $vector2:ty) => {
+        impl HexVector3<$scalar> for $vector3 {
+            fn from_xyz(x: $scalar, y: $scalar, z: $scalar) -> Self {
+                <$vector3>::new(x, y, z)
+            }
+
+            fn xyz(&self) -> [$scalar; 3] {
+                self.to_array()
+            }
+        }
+
+        impl HexVector2<$scalar> for $vector2 {
+            fn from_xy(x: $scalar, y: $scalar) -> Self {
+                <$vector2>::new(x, y)
+            }
+
+            fn xy(&self) -> [$scalar; 2] {
+                self.to_array()
+            }
+        }
+    };
+}
+
+#[cfg(feature = "glam")]
+impl_glam!(f32, glam::Vec3, glam::Vec2);
+#[cfg(feature = "glam")]
+impl_glam!(f64, glam::DVec3, glam::DVec2);
+
+#[cfg(feature = "glam")]
+impl HexVector3<f32> for glam::Vec3A {
+    fn from_xyz(x: f32, y: f32, z: f32) -> Self {
+        glam::Vec3A::new(x, y, z)
+    }
+
+    fn xyz(&self) -> [f32; 3] {
+        self.to_array()
+    }
+}
+
+// mint
+#[cfg(feature = "mint")]
+impl<F: HexFloat> HexVector3<F> for mint::Vector3<F> {
+    fn from_xyz(x: F, y: F, z: F) -> Self {
+        mint::Vector3 { x, y, z }
+    }
+
+    fn xyz(&self) -> [F; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<F: HexFloat> HexVector3<F> for mint::Point3<F> {
+    fn from_xyz(x: F, y: F, z: F) -> Self {
+        mint::Point3 { x, y, z }
+    }
+
+    fn xyz(&self) -> [F; 3] {
+        [self.x, self.y, self.z]
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<F: HexFloat> HexVector2<F> for mint::Vector2<F> {
+    fn from_xy(x: F, y: F) -> Self {
+        mint::Vector2 { x, y }
+    }
+
+    fn xy(&self) -> [F; 2] {
+        [self.x, self.y]
+    }
+}
+
+#[cfg(feature = "mint")]
+impl<F: HexFloat> HexVector2<F> for mint::Point2<F> {
+    fn from_xy(x: F, y: F) -> Self {
+        mint::Point2 { x, y }
+    }
+
+    fn xy(&self) -> [F; 2] {
+        [self.x, self.y]
+    }
+}
+
+// the vector types used internally, so the math doesn't depend on which vector library is enabled
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(crate) struct Vector3<F> {
+    pub(crate) x: F,
+    pub(crate) y: F,
+    pub(crate) z: F,
+}
+
+impl<F: HexFloat> Vector3<F> {
+    pub(crate) fn new(x: F, y: F, z: F) -> Vector3<F> {
+        Vector3 { x, y, z }
+    }
+
+    pub(crate) fn from_hex_vector<V: HexVector3<F>>(vector: V) -> Vector3<F> {
+        let [x, y, z] = vector.xyz();
+        Vector3 { x, y, z }
+    }
+
+    pub(crate) fn to_hex_vector<V: HexVector3<F>>(self) -> V {
+        V::from_xyz(self.x, self.y, self.z)
+    }
+
+    pub(crate) fn cast<G: HexFloat>(self) -> Vector3<G> {
+        Vector3::new(G::from_f64(self.x.as_f64()), G::from_f64(self.y.as_f64()), G::from_f64(self.z.as_f64()))
+    }
+
+    pub(crate) fn dot(self, other: Vector3<F>) -> F {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub(crate) fn cross(self, other: Vector3<F>) -> Vector3<F> {
+        Vector3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    pub(crate) fn magnitude(self) -> F {
+        self.dot(self).sqrt()
+    }
+
+    pub(crate) fn normalize(self) -> Vector3<F> {
+        self / self.magnitude()
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub(crate) struct Vector2<F> {
+    pub(crate) x: F,
+    pub(crate) y: F,
+}
+
+impl<F: HexFloat> Vector2<F> {
+    pub(crate) fn new(x: F, y: F) -> Vector2<F> {
+        Vector2 { x, y }
+    }
+
+    pub(crate) fn from_hex_vector<V: HexVector2<F>>(vector: V) -> Vector2<F> {
+        let [x, y] = vector.xy();
+        Vector2 { x, y }
+    }
+
+    pub(crate) fn to_hex_vector<V: HexVector2<F>>(self) -> V {
+        V::from_xy(self.x, self.y)
+    }
+
+    pub(crate) fn cast<G: HexFloat>(self) -> Vector2<G> {
+        Vector2::new(G::from_f64(self.x.as_f64()), G::from_f64(self.y.as_f64()))
+    }
+}
+
+macro_rules! impl_vector_ops {
+    ($vector:ident, $($component:ident),+) => {
+        impl<F: HexFloat> ops::Add for $vector<F> {
+            type Output = $vector<F>;
+
+            fn add(self, other: $vector<F>) -> $vector<F> {
+                $vector { $($component: self.$component + other.$component),+ }
+            }
+        }
+
+        impl<F: HexFloat> ops::Sub for $vector<F> {
+            type Output = $vector<F>;
+
+            fn sub(self, other: $vector<F>) -> $vector<F> {
+                $vector { $($component: self.$component - other.$component),+ }
+            }
+        }
+
+        impl<F: HexFloat> ops::Neg for $vector<F> {
+            type Output = $vector<F>;
+
+            fn neg(self) -> $vector<F> {
+                $vector { $($component: -self.$component),+ }
+            }
+        }
+
+        impl<F: HexFloat> ops::Mul<F> for $vector<F> {
+            type Output = $vector<F>;
+
+            fn mul(self, other: F) -> $vector<F> {
+                $vector { $($component: self.$component * other),+ }
+            }
+        }
+
+        impl<F: HexFloat> ops::Div<F> for $vector<F> {
+            type Output = $vector<F>;
+
+            fn div(self, other: F) -> $vector<F> {
+                $vector { $($component: self.$component / other),+ }
+            }
+        }
+    };
+}
+
+impl_vector_ops!(Vector3, x, y, z);
+impl_vector_ops!(Vector2, x, y);
@@ -1,6 +1,9 @@
 use crate::*;
+use crate::hex_parse::parse_components;
+use std::{fmt, str::FromStr};
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HexVertex {
     three_q: i32,
     three_r: i32,
@@ -38,6 +41,8 @@ impl HexVertex {
         (self.three_q() - 1).rem_euclid(3) == 0 && (self.three_r() - 1).rem_euclid(3) == 0
     }
 
+    /// Returns outgoing edge `i` of `self`, one of the three edges leaving it in CCW order starting from `i = 0`.
+    /// Edge `i` of a vertex on the positive basis points along `get_unit_coord(2*i)`, and on the negative basis along `get_unit_coord(2*i + 1)`.
     pub fn get_outgoing_edge(&self, i: i32) -> HexHalfEdge {
         let direction =
             if self.on_positive_basis() {
@@ -48,6 +53,36 @@ impl HexVertex {
                 panic!("HexVertex is not on a vertex");
             };
         
+        self.edge_along(direction)
+    }
+
+    pub fn get_incoming_edge(&self, i: i32) -> HexHalfEdge {
+        self.get_outgoing_edge(i).twin()
+    }
+
+    /// Returns the edge leaving `self` toward `corner`, along `HexVertex::get_unit_coord(corner)`.
+    /// Each vertex has only three edges; when `self` has none toward `corner`, returns the one a sixth-turn CW from it.
+    pub fn get_outgoing_edge_toward<C: Into<HexCorner>>(&self, corner: C) -> HexHalfEdge {
+        // vertices on the positive basis have edges toward the even corners, and those on the negative basis toward the odd ones
+        let corner = corner.into();
+        let direction =
+            if self.on_positive_basis() {
+                HexVertex::get_unit_coord(corner - corner.index() % 2)
+            } else if self.on_negative_basis() {
+                HexVertex::get_unit_coord(corner - (corner.index() + 1) % 2)
+            } else {
+                panic!("HexVertex is not on a vertex");
+            };
+
+        self.edge_along(direction)
+    }
+
+    /// Returns the edge arriving at `self` from `corner`, the twin of `get_outgoing_edge_toward(corner)`.
+    pub fn get_incoming_edge_from<C: Into<HexCorner>>(&self, corner: C) -> HexHalfEdge {
+        self.get_outgoing_edge_toward(corner).twin()
+    }
+
+    fn edge_along(&self, direction: HexVertex) -> HexHalfEdge {
         HexHalfEdge::new(
             *self,
             HexVertex::new(
@@ -57,20 +92,42 @@ impl HexVertex {
         )
     }
 
-    pub fn get_incoming_edge(&self, i: i32) -> HexHalfEdge {
-        self.get_outgoing_edge(i).twin()
+    pub fn get_unit_coord<C: Into<HexCorner>>(i: C) -> HexVertex {
+        match i.into() {
+            HexCorner::Zero => HexVertex::new(2, -1),
+            HexCorner::One => HexVertex::new(1, 1),
+            HexCorner::Two => HexVertex::new(-1, 2),
+            HexCorner::Three => HexVertex::new(-2, 1),
+            HexCorner::Four => HexVertex::new(-1, -1),
+            HexCorner::Five => HexVertex::new(1, -2),
+        }
     }
 
-    pub fn get_unit_coord(i: i32) -> HexVertex {
-        match i.rem_euclid(6) {
-            0 => HexVertex::new(2, -1),
-            1 => HexVertex::new(1, 1),
-            2 => HexVertex::new(-1, 2),
-            3 => HexVertex::new(-2, 1),
-            4 => HexVertex::new(-1, -1),
-            5 => HexVertex::new(1, -2),
-            _ => panic!("logic error; rem_euclid(6) should return one of the previous cases"),
-        }
+    /// Returns the three hexes that share `self` as a vertex.
+    pub fn hexes(&self) -> [HexCoord; 3] {
+        // the hexes are a third of a step from the vertex along the three edges of the opposite basis
+        let first = if self.on_positive_basis() {
+            1
+        } else if self.on_negative_basis() {
+            0
+        } else {
+            panic!("HexVertex is not on a vertex");
+        };
+
+        [first, first + 2, first + 4].map(|i| {
+            let offset = HexVertex::get_unit_coord(i);
+            HexCoord::new(
+                (self.three_q() + offset.three_q()) / 3,
+                (self.three_r() + offset.three_r()) / 3,
+            )
+        })
+    }
+
+    /// Returns the diagonal hex of `hex` that lies beyond `self`, at the far end of the edge leading away from `hex` at `self`, or `None` if `self` isn't a vertex of `hex`.
+    pub fn diagonal_beyond(&self, hex: HexCoord) -> Option<HexCoord> {
+        HexCorner::iter()
+            .find(|&corner| hex.get_vertex(corner) == *self)
+            .map(|corner| hex.get_diagonal(corner))
     }
 
     pub fn translate(&self, translation: &HexCoord) -> HexVertex {
@@ -79,6 +136,46 @@ impl HexVertex {
             self.three_r() + 3 * translation.r(),
         )
     }
+
+    /// Returns `self` translated by `translation`, or `None` if any coordinate of the result is further than `HexCoord::MAX_COORDINATE + 2/3` from zero.
+    /// That is the extent of the vertices of hexes within `HexCoord::MAX_COORDINATE`.
+    pub fn checked_translate(&self, translation: &HexCoord) -> Option<HexVertex> {
+        let max = 3 * HexCoord::MAX_COORDINATE as i64 + 2;
+        let three_q = self.three_q() as i64 + 3 * translation.q() as i64;
+        let three_r = self.three_r() as i64 + 3 * translation.r() as i64;
+        if three_q.abs() > max || three_r.abs() > max || (three_q + three_r).abs() > max {
+            return None;
+        }
+
+        Some(HexVertex::new(three_q as i32, three_r as i32))
+    }
+}
+
+/// Writes the vertex's coordinates in thirds, such as `(2/3, -1/3, -1/3)`.
+impl fmt::Display for HexVertex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let third = |n: i32| if n % 3 == 0 { (n / 3).to_string() } else { format!("{}/3", n) };
+        write!(f, "({}, {}, {})", third(self.three_q()), third(self.three_r()), third(self.three_s()))
+    }
 }
 
+/// Parses coordinates in the format written by `Display`, with or without the parentheses or the *s* component.
+/// Components may be integers or thirds, but must name a vertex rather than a hex center or some other point.
+impl FromStr for HexVertex {
+    type Err = HexParseError;
 
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (three_q, three_r) = parse_components(s, |component| match component.split_once('/') {
+            Some((numerator, "3")) => numerator.trim().parse().ok(),
+            Some(_) => None,
+            None => component.parse::<i32>().ok()?.checked_mul(3),
+        })?;
+
+        // vertices have q and r one third past a whole number in the same direction; hex centers have them on whole numbers
+        let residue = three_q.rem_euclid(3);
+        if residue == 0 || three_r.rem_euclid(3) != residue {
+            return Err(HexParseError::NotOnGrid(s.to_string()));
+        }
+        Ok(HexVertex::new(three_q, three_r))
+    }
+}
@@ -3,61 +3,151 @@
 //! ## Coordinates and Basic Usage
 //! SturdyHex uses the axial coordinate system from Amit Patel's [excellent resource](https://www.redblobgames.com/grids/hexagons/) on hex grids.  The `hex_field` struct relates a worldspace coordinate system with a hex coordinate system:
 //! ```rust
+//! # #[cfg(feature = "cgmath")] {
 //! use sturdy_hex::{HexField, HexCoord};
 //! use cgmath::{Vector3, Zero};
 //! 
 //! let hex_field = HexField::new(
-//! 	Vector3::<f32>::zero(), //center of hex (0, 0, 0)
-//! 	Vector3::<f32>::new(0.0, 0.0, 1.0), //up direction
-//! 	Vector3::<f32>::new(0.0, 1.0, 0.0), //center of hex with coord (0, 1, -1)
+//!     Vector3::<f32>::zero(), //center of hex (0, 0, 0)
+//!     Vector3::<f32>::new(0.0, 0.0, 1.0), //up direction
+//!     Vector3::<f32>::new(0.0, 1.0, 0.0), //center of hex with coord (0, 1, -1)
 //! );
-
+//!
 //! let hex_containing_origin = hex_field.get_hex_coord(Vector3::<f32>::zero());
 //! let position_of_center = hex_field.get_position(HexCoord::ZERO);
+//! # }
 //! ```
-//! You can choose between Amit Patel's "flat-top" and "pointy-top" orientations by your choice of the center for hex *(0, -1, 1)*; in the example above, it's on the *y*-axis, providing flat-top coordinates.
-
-//! SturdyHex currently uses [cgmath](https://docs.rs/cgmath/latest/cgmath/) for vector math, but I intend to switch to [glam](https://docs.rs/glam/latest/glam/) and provide [mint](https://docs.rs/mint/latest/mint/) compatibility.
-
+//! You can choose between Amit Patel's "flat-top" and "pointy-top" orientations by your choice of the center for hex *(0, -1, 1)*; in the example above, it's on the *y*-axis, providing flat-top coordinates.  Alternatively, `HexField::with_layout` takes an explicit `HexOrientation` and `Handedness`.
+//!
+//! `HexDirection` and `HexCorner` name the neighbor and vertex indices taken by methods like `get_neighbor` and `get_vertex`, with constants such as `HexDirection::FLAT_NORTH` and `HexCorner::POINTY_NORTH` for each orientation.  `get_diagonal` and `diagonals` find the six hexes at distance 2 that lie between pairs of neighbors, and `diagonal_distance` counts moves when each step may go to a neighbor or a diagonal.
+//!
+//! For 2D work such as UI and screen-space picking, `HexField2` provides the same conversions with stretched hexes and y-down coordinates.  `OffsetCoord` and `DoubledCoord` convert to and from the offset and doubled coordinates used by many tile editors.
+//!
+//! `HexCoord`, `HexVertex`, `HexHalfEdge`, and `HexTransform` implement `Display` and `FromStr`, so they can be logged and read back from strings like `(1, -2, 1)`.
+//! ### Precision and Large Grids
+//! `HexField`, `HexField2`, and `HexCoordFraction` use `f32` by default, but are generic over `HexFloat` so maps far from the origin can use `f64`.
+//!
+//! Hexes whose coordinates are within `HexCoord::MAX_COORDINATE` of zero are guaranteed exact through distances, rotations, reflections, transforms, vertex and edge conversions, and chunking, as listed on that constant.  `checked_add`, `checked_sub`, `checked_mul`, and `HexCoordFraction::try_round` report results that leave those bounds.
+//!
+//! For grids beyond them, `HexCoord64`, `HexVertex64`, `HexHalfEdge64`, and `HexChunker64` provide the same algorithms with 64-bit coordinates, converting to and from their 32-bit counterparts.
+//! ### Vector Types and Serialization
+//! SturdyHex accepts and returns the vector types of [cgmath](https://docs.rs/cgmath/latest/cgmath/), [glam](https://docs.rs/glam/latest/glam/), and [mint](https://docs.rs/mint/latest/mint/) behind the `cgmath`, `glam`, and `mint` cargo features, as well as plain arrays.  Only `cgmath` is enabled by default.  Vector types are inferred from the arguments you pass; when a `HexField` or `HexField2` type is written without one, it uses cgmath's vectors, or plain arrays if the `cgmath` feature is disabled.
+//!
+//! The `serde` feature adds serialization for coordinates, transforms, shapes, and `HashMapHexData`.  In human-readable formats, `HexCoord` is written as a string like `"1,-2"` so it can be a JSON map key.
+//!
 //! ## Overview of Advanced Features
 //! ### Grid traversal
 //! SturdyHex provides methods to interact with a hex grid as a [doubly-connected edge list](https://en.wikipedia.org/wiki/Doubly_connected_edge_list).  Because hex grids are regular, all connectivity information is implicit; we can calculate how hexes, vertices, and edges connect, so we don't need to store any connection information.
-
+//!
 //! See `HexCoord`, `HexVertex`, and `HexHalfEdge` for the relevant methods.
+//!
+//! For continuous motion, `HexField::traverse_segment` visits the hexes a worldspace segment passes through along with the edges it crosses, optionally including hexes it only touches at a vertex.
 //! ### Chunking
 //! Sometimes it's useful to partition a hex grid into hex-shaped chunks.  Unfortunately, this is not as straightforward as it is with squares and square-shaped chunks.  You can create a `HexChunker` instance with a specified chunk radius to perform conversions from hex coordinate to chunk coordinate and back.  SturdyHex uses Sander Ever's [algorithm](https://observablehq.com/@sanderevers/hexagon-tiling-of-an-hexagonal-grid) for converting in the hex-to-chunk direction.
 //! ### Transforms
 //! The `HexTransform` struct represents a translation and rotation in hex coordinates.  You can apply them to hexes, vertices, edges, and other transforms via the `*` operator.
 //! ### Shapes
 //! The `HexShape` class stores a list of hex coordinates and supports the `contains` operation.  You can create a `HexShapeView` that references a `HexShape` and encodes a transformation on it; this allows you to query against a shape without copying its data.
+//!
+//! Shapes also support connectivity queries, morphological operations, and canonical forms for comparing shapes up to rotation and reflection.  `HexShape::polyhexes` enumerates every shape of a given size, and `HexShape::pack` finds the ways to tile a region exactly with a set of pieces.
 //! ### Range Iteration
 //! You can create a `HexRangeIterator` instance with a center coordinate and radius to iterate over the corresponding hexagonal range of hexes.
+//! ### Picking
+//! `HexField::raycast` intersects a worldspace ray with the plane of the grid, as for mouse picking, and returns the hex it hits along with the intersection point.  `raycast_vertex` and `raycast_half_edge` return the nearest vertex or edge instead.
+//! ### Locating Points Within Hexes
+//! `HexField::get_nearest_vertex` and `get_nearest_edge`, and `HexCoordFraction::round_to_vertex` and `round_to_edge`, find the vertex or edge nearest to a point along with the distance to it.
+//!
+//! `HexCoordFraction::sector` finds which of the six triangles between a hex's center and its edges contains a point, along with the point's barycentric coordinates in it.
+//! ### Meshes
+//! `HexMeshBuilder` generates engine-agnostic vertex positions, normals, UVs, and triangle indices for hex tops on a `HexField`, optionally with side walls between hexes at different heights.
+//! ### Culling
+//! `HexField::hexes_in_polygon` and `HexField::hexes_in_frustum` iterate over the hexes that may be visible in a convex region of the grid.
+//! ### SVG Export
+//! `HexSvg` renders hexes, shapes, hex maps given as pairs of hexes and values, edges, and vertices to an SVG string laid out by a `HexField2`, for documentation and snapshot tests.
+//! ### ASCII Maps
+//! For small boards in tests, `render_ascii` and `parse_ascii` convert hex maps to and from staggered rows of characters.
+//! ### Binary Encoding
+//! `encode_hex_data` stores a hex map, given as pairs of hexes and values, in a compact, versioned binary format, and `decode_hex_data` reads it back into any `HexData`.  Maps are stored with a dense hexagon or parallelogram domain, or as a run-length encoded sparse list of hexes.  Values are written by a `HexValueCodec` you supply.
 
 pub mod hex_coord;
+pub mod hex_coord64;
+pub mod hex_direction;
 pub mod hex_coord_fraction;
+pub mod hex_offset;
+pub mod hex_parse;
+pub mod hex_float;
+pub mod hex_vector;
 pub mod hex_half_edge;
 pub mod hex_vertex;
 
 pub mod hex_transform;
 pub mod hex_shape;
+pub mod hex_polyhex;
+pub mod hex_packing;
 pub mod hex_range_iterator;
 
 pub mod hex_field;
+pub mod hex_field2;
+pub mod hex_culling;
+pub mod hex_segment;
+pub mod hex_svg;
+pub mod hex_ascii;
+pub mod hex_mesh;
 pub mod hex_data;
 pub mod hex_chunker;
+pub mod hex_binary;
+#[cfg(feature = "serde")]
+mod hex_serde;
 
 
 pub use hex_coord::HexCoord;
+pub use hex_coord64::HexCoord64;
+pub use hex_coord64::HexVertex64;
+pub use hex_coord64::HexHalfEdge64;
+pub use hex_coord64::HexChunker64;
+pub use hex_coord64::HexOutOfBoundsError;
+pub use hex_direction::HexDirection;
+pub use hex_direction::HexCorner;
 pub use hex_coord_fraction::HexCoordFraction;
+pub use hex_offset::OffsetCoord;
+pub use hex_offset::OffsetLayout;
+pub use hex_offset::DoubledCoord;
+pub use hex_offset::DoubledLayout;
+pub use hex_parse::HexParseError;
+pub use hex_float::HexFloat;
+pub use hex_vector::HexVector3;
+pub use hex_vector::HexVector2;
 pub use hex_half_edge::HexHalfEdge;
 pub use hex_vertex::HexVertex;
 
 pub use hex_transform::HexTransform;
 pub use hex_shape::HexShape;
 pub use hex_shape::HexShapeView;
+pub use hex_polyhex::PolyhexKind;
+pub use hex_packing::HexPackingOptions;
 pub use hex_range_iterator::HexRangeIterator;
 
 pub use hex_field::HexField;
+pub use hex_field::HexOrientation;
+pub use hex_field::Handedness;
+pub use hex_field2::HexField2;
+pub use hex_culling::HexPolygonIterator;
+pub use hex_segment::HexSegmentIterator;
+pub use hex_segment::HexSegmentStep;
+pub use hex_svg::HexSvg;
+pub use hex_svg::HexSvgStyle;
+pub use hex_ascii::render_ascii;
+pub use hex_ascii::render_ascii_shape;
+pub use hex_ascii::parse_ascii;
+pub use hex_ascii::parse_ascii_shape;
+pub use hex_ascii::HexAsciiError;
+pub use hex_mesh::HexMesh;
+pub use hex_mesh::HexMeshBuilder;
 pub use hex_data::HexData;
 pub use hex_data::HashMapHexData;
 pub use hex_chunker::HexChunker;
+pub use hex_binary::encode_hex_data;
+pub use hex_binary::decode_hex_data;
+pub use hex_binary::HexBinaryError;
+pub use hex_binary::HexDomain;
+pub use hex_binary::HexValueCodec;